@@ -1,24 +1,42 @@
 pub const PLATFORM_SEED: &[u8] = b"platform";
 pub const COMPANY_SEED: &[u8] = b"company";
 pub const VESTING_SEED: &[u8] = b"vesting";
+pub const GRANTS_SEED: &[u8] = b"grants";
 pub const BANKING_SEED: &[u8] = b"banking";
 pub const STAKING_SEED: &[u8] = b"staking";
 pub const LOAN_SEED: &[u8] = b"loan";
 pub const SAVINGS_SEED: &[u8] = b"savings";
 pub const PROFILE_SEED: &[u8] = b"profile";
+pub const MILESTONE_SEED: &[u8] = b"milestone";
+pub const METRIC_SEED: &[u8] = b"metric";
+pub const DISTRIBUTOR_SEED: &[u8] = b"distributor";
+pub const DISTRIBUTOR_CLAIM_SEED: &[u8] = b"distributor_claim";
+pub const TEMPLATE_SEED: &[u8] = b"template";
+pub const EMPLOYEE_SEED: &[u8] = b"employee";
+pub const PAYROLL_SEED: &[u8] = b"payroll";
 
 pub const MAX_COMPANY_NAME_LENGTH: usize = 32;
 pub const MAX_SYMBOL_LENGTH: usize = 8;
+pub const MAX_DEPARTMENT_LENGTH: usize = 32;
+pub const MAX_ROLE_LENGTH: usize = 32;
 pub const MAX_VESTING_SCHEDULES_PER_USER: usize = 10;
 pub const MAX_LOANS_PER_USER: usize = 5;
 pub const MAX_SAVINGS_ACCOUNTS_PER_USER: usize = 3;
+pub const MAX_MILESTONES_PER_SCHEDULE: u8 = 16;
+pub const MAX_PERFORMANCE_BANDS: usize = 4;
+pub const MAX_COMPANY_SIGNERS: usize = 5;
+pub const MAX_CRANK_TIP_BPS: u16 = 100; // 1%
+pub const MAX_WITHHOLDING_BPS: u16 = 5000; // 50%
 
 pub const MIN_VESTING_DURATION: i64 = 86400; // 1 day
 pub const MAX_VESTING_DURATION: i64 = 126144000; // 4 years
 pub const MIN_CLIFF_DURATION: i64 = 0;
 pub const MAX_CLIFF_DURATION: i64 = 31536000; // 1 year
 
-pub const MIN_STAKE_AMOUNT: u64 = 1_000_000; // 1 token (6 decimals)
+// Minimums below are in base units of a BASE_TOKEN_DECIMALS mint; scale_to_decimals
+// converts them for mints with other decimals
+pub const BASE_TOKEN_DECIMALS: u8 = 6; // Decimals of mints created by create_company
+pub const MIN_STAKE_AMOUNT: u64 = 1_000_000; // 1 token
 pub const MIN_LOAN_AMOUNT: u64 = 10_000_000; // 10 tokens
 pub const MIN_SAVINGS_DEPOSIT: u64 = 1_000_000; // 1 token
 
@@ -33,6 +51,11 @@ pub const MIN_COLLATERAL_RATIO: u16 = 12500; // 125%
 
 pub const SECONDS_PER_DAY: i64 = 86400;
 pub const SECONDS_PER_YEAR: i64 = 31536000;
+pub const SECONDS_PER_MONTH: i64 = 2629746; // average Gregorian month
+pub const SECONDS_PER_QUARTER: i64 = 3 * SECONDS_PER_MONTH;
+pub const MIN_RELEASE_INTERVAL: i64 = SECONDS_PER_DAY;
+pub const MAX_EXERCISE_WINDOW: i64 = 10 * SECONDS_PER_YEAR;
+pub const DOUBLE_TRIGGER_WINDOW: i64 = SECONDS_PER_YEAR; // Termination must follow change of control within this
 pub const BASIS_POINTS: u64 = 10000;
 
 pub const PLATFORM_FEE_BPS: u16 = 25; // 0.25%
@@ -79,4 +79,127 @@ pub enum BankingVestingError {
     
     #[msg("Invalid timestamp")]
     InvalidTimestamp,
+    
+    #[msg("Mint does not match the company token")]
+    InvalidMint,
+    
+    #[msg("Vesting schedule has been revoked")]
+    VestingRevoked,
+    
+    #[msg("Operation not supported for this vesting type")]
+    InvalidVestingType,
+    
+    #[msg("Invalid milestone weight")]
+    InvalidMilestoneWeight,
+    
+    #[msg("Milestone already achieved")]
+    MilestoneAlreadyAchieved,
+    
+    #[msg("Invalid performance bands")]
+    InvalidPerformanceBands,
+    
+    #[msg("Performance target already set")]
+    PerformanceTargetAlreadySet,
+    
+    #[msg("Too many active vesting schedules for this beneficiary")]
+    TooManyVestingSchedules,
+    
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    
+    #[msg("Company approval required for this transfer")]
+    TransferApprovalRequired,
+    
+    #[msg("Invalid transfer recipient")]
+    InvalidTransferRecipient,
+    
+    #[msg("Crank tip exceeds the maximum allowed")]
+    InvalidCrankTip,
+    
+    #[msg("Change of control already declared")]
+    ChangeOfControlAlreadyDeclared,
+    
+    #[msg("Acceleration conditions not met")]
+    AccelerationNotTriggered,
+    
+    #[msg("Invalid acceleration policy")]
+    InvalidAccelerationPolicy,
+    
+    #[msg("Option grants must be exercised rather than claimed")]
+    OptionExerciseRequired,
+    
+    #[msg("Invalid option terms")]
+    InvalidOptionTerms,
+    
+    #[msg("Exercise window has closed")]
+    ExerciseWindowClosed,
+    
+    #[msg("Exercise window is still open")]
+    ExerciseWindowOpen,
+    
+    #[msg("Amendment would reduce already vested tokens")]
+    AmendmentReducesVested,
+    
+    #[msg("Vesting schedule is already paused")]
+    ScheduleAlreadyPaused,
+    
+    #[msg("Vesting schedule is not paused")]
+    ScheduleNotPaused,
+    
+    #[msg("Vesting schedule still has unsettled tokens")]
+    ScheduleNotSettled,
+    
+    #[msg("Template has changed since it was last read")]
+    TemplateVersionMismatch,
+    
+    #[msg("Escrow tokens are still staked")]
+    EscrowStillStaked,
+    
+    #[msg("Invalid withholding terms")]
+    InvalidWithholdingTerms,
+    
+    #[msg("Withholding account does not match the withholding destination")]
+    InvalidWithholdingAccount,
+    
+    #[msg("Invalid supply policy")]
+    InvalidSupplyPolicy,
+    
+    #[msg("Minting would exceed the company's supply cap")]
+    SupplyCapExceeded,
+    
+    #[msg("Company no longer holds mint authority")]
+    MintAuthorityRevoked,
+    
+    #[msg("Transfer-fee mints cannot move between program escrows")]
+    TransferFeeNotSupported,
+    
+    #[msg("Invalid employee details")]
+    InvalidEmployeeDetails,
+    
+    #[msg("Employee has been terminated")]
+    EmployeeTerminated,
+    
+    #[msg("Employee has not been terminated with a revocation policy")]
+    EmployeeNotTerminated,
+    
+    #[msg("Vesting schedule is already linked to an employee")]
+    ScheduleAlreadyLinked,
+    
+    #[msg("Invalid company signer set")]
+    InvalidSignerSet,
+    
+    #[msg("Not enough company signers approved this action")]
+    InsufficientApprovals,
+    
+    #[msg("Distributor claim window has closed")]
+    DistributorExpired,
+    
+    #[msg("Distributor claim window is still open")]
+    DistributorNotExpired,
+    
+    #[msg("Vested tokens are staked, unstake the escrow before paying them out")]
+    TokensStaked,
+    
+    #[msg("Release terms do not fit the vesting type")]
+    InvalidReleaseTerms,
 }
@@ -17,13 +17,102 @@ pub struct CompanyCreated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CompanySupplyMinted {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub total_supply: u64,
+    pub issued_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanyMintAuthorityRevoked {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub total_supply: u64,
+    pub issued_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanyAuthorityProposed {
+    pub company: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanyAuthorityTransferred {
+    pub company: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanySignersUpdated {
+    pub company: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub approval_threshold: u8,
+    pub grant_approval_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmployeeOnboarded {
+    pub employee: Pubkey,
+    pub company: Pubkey,
+    pub wallet: Pubkey,
+    pub department: String,
+    pub role: String,
+    pub hire_date: i64,
+    pub employees_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmployeeUpdated {
+    pub employee: Pubkey,
+    pub company: Pubkey,
+    pub department: String,
+    pub role: String,
+    pub status: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmployeeOffboarded {
+    pub employee: Pubkey,
+    pub company: Pubkey,
+    pub wallet: Pubkey,
+    pub termination_date: i64,
+    pub leaver_policy: Option<String>,
+    pub schedules_linked: u32,
+    pub employees_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmployeeScheduleLinked {
+    pub employee: Pubkey,
+    pub schedule: Pubkey,
+    pub schedules_linked: u32,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct VestingScheduleCreated {
     pub schedule: Pubkey,
     pub company: Pubkey,
     pub beneficiary: Pubkey,
+    pub grant_id: u64,
     pub total_amount: u64,
     pub vesting_type: String,
+    pub release_interval: i64,
     pub timestamp: i64,
 }
 
@@ -32,6 +121,287 @@ pub struct TokensClaimed {
     pub schedule: Pubkey,
     pub beneficiary: Pubkey,
     pub amount: u64,
+    pub withheld_amount: u64,
+    pub withholding_account: Pubkey,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingScheduleRevoked {
+    pub schedule: Pubkey,
+    pub company: Pubkey,
+    pub beneficiary: Pubkey,
+    pub leaver_policy: String,
+    pub vested_amount: u64,
+    pub paid_out: u64,
+    pub returned_amount: u64,
+    pub revoked_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingScheduleTransferred {
+    pub old_schedule: Pubkey,
+    pub new_schedule: Pubkey,
+    pub company: Pubkey,
+    pub old_beneficiary: Pubkey,
+    pub new_beneficiary: Pubkey,
+    pub amount_moved: u64,
+    pub approved_by: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferApprovalPolicyUpdated {
+    pub company: Pubkey,
+    pub require_approval: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimSettingsUpdated {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub permissionless_claims: bool,
+    pub crank_tip_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimCranked {
+    pub schedule: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+    pub tip: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChangeOfControlDeclared {
+    pub company: Pubkey,
+    pub declared_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccelerationPolicySet {
+    pub schedule: Pubkey,
+    pub trigger: String,
+    pub acceleration_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingAccelerated {
+    pub schedule: Pubkey,
+    pub company: Pubkey,
+    pub beneficiary: Pubkey,
+    pub trigger: String,
+    pub accelerated_amount: u64,
+    pub change_of_control_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OptionTermsSet {
+    pub schedule: Pubkey,
+    pub strike_price: u64,
+    pub payment_mint: Pubkey,
+    pub exercise_window: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OptionsExercised {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub quantity: u64,
+    pub exercise_cost: u64,
+    pub payment_mint: Pubkey,
+    pub total_exercised: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExpiredOptionsReclaimed {
+    pub schedule: Pubkey,
+    pub company: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingScheduleAmended {
+    pub schedule: Pubkey,
+    pub company: Pubkey,
+    pub beneficiary: Pubkey,
+    pub previous_total_amount: u64,
+    pub total_amount: u64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub amendment_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingSchedulePaused {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub paused_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingScheduleResumed {
+    pub schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub paused_duration: i64,
+    pub total_paused_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingScheduleClosed {
+    pub schedule: Pubkey,
+    pub company: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub milestones_closed: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingTemplateUpdated {
+    pub template: Pubkey,
+    pub company: Pubkey,
+    pub template_id: u32,
+    pub version: u32,
+    pub vesting_type: String,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingEscrowStaked {
+    pub schedule: Pubkey,
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingEscrowUnstaked {
+    pub schedule: Pubkey,
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeRewardsClaimed {
+    pub schedule: Pubkey,
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithholdingTermsUpdated {
+    pub company: Pubkey,
+    pub schedule: Option<Pubkey>,
+    pub withholding_bps: u16,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithholdingRemitted {
+    pub company: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneAdded {
+    pub milestone: Pubkey,
+    pub schedule: Pubkey,
+    pub index: u8,
+    pub weight_bps: u16,
+    pub attester: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneAchieved {
+    pub milestone: Pubkey,
+    pub schedule: Pubkey,
+    pub index: u8,
+    pub weight_bps: u16,
+    pub achieved_bps: u16,
+    pub attested_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceMetricCreated {
+    pub metric: Pubkey,
+    pub company: Pubkey,
+    pub metric_id: u32,
+    pub reporter: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceMetricReported {
+    pub metric: Pubkey,
+    pub reporter: Pubkey,
+    pub value: u64,
+    pub peak_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceTargetSet {
+    pub schedule: Pubkey,
+    pub metric: Pubkey,
+    pub band_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MerkleDistributorCreated {
+    pub distributor: Pubkey,
+    pub company: Pubkey,
+    pub distributor_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MerkleGrantMaterialized {
+    pub distributor: Pubkey,
+    pub claim: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributorClawedBack {
+    pub distributor: Pubkey,
+    pub company: Pubkey,
+    pub amount: u64,
     pub total_claimed: u64,
     pub timestamp: i64,
 }
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct DeclareChangeOfControl<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    /// The authority may itself be a governance or multisig PDA
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized,
+        constraint = company.change_of_control_at == 0 @ BankingVestingError::ChangeOfControlAlreadyDeclared
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAccelerationPolicy<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked,
+        constraint = vesting_schedule.accelerated_at == 0 @ BankingVestingError::InvalidAccelerationPolicy
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Permissionless, any keeper can settle single-trigger acceleration per schedule
+#[derive(Accounts)]
+pub struct AccelerateVestingSchedule<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.change_of_control_at != 0 @ BankingVestingError::AccelerationNotTriggered
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+}
+
+pub fn declare_change_of_control(ctx: Context<DeclareChangeOfControl>) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    company.change_of_control_at = clock.unix_timestamp;
+    
+    emit!(ChangeOfControlDeclared {
+        company: company.key(),
+        declared_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn set_acceleration_policy(
+    ctx: Context<SetAccelerationPolicy>,
+    acceleration_policy: AccelerationPolicy,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    validate_acceleration_policy(&acceleration_policy)?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    vesting_schedule.acceleration_policy = acceleration_policy;
+    
+    emit!(AccelerationPolicySet {
+        schedule: vesting_schedule.key(),
+        trigger: acceleration_trigger_str(acceleration_policy.trigger).to_string(),
+        acceleration_bps: acceleration_policy.acceleration_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn accelerate_vesting_schedule(ctx: Context<AccelerateVestingSchedule>) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let company = &ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    // Double-trigger schedules accelerate on revocation instead
+    require!(
+        vesting_schedule.acceleration_policy.trigger == AccelerationTrigger::SingleTrigger
+            && vesting_schedule.accelerated_at == 0
+            && vesting_schedule.created_at <= company.change_of_control_at,
+        BankingVestingError::AccelerationNotTriggered
+    );
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let accelerated_amount = apply_acceleration(vesting_schedule, company.change_of_control_at)?;
+    
+    emit!(VestingAccelerated {
+        schedule: vesting_schedule.key(),
+        company: company.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        trigger: acceleration_trigger_str(AccelerationTrigger::SingleTrigger).to_string(),
+        accelerated_amount,
+        change_of_control_at: company.change_of_control_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::constants::*;
@@ -25,27 +25,29 @@ pub struct DepositFunds<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = banking_account
+        associated_token::authority = banking_account,
+        associated_token::token_program = token_program
     )]
-    pub platform_token_account: Account<'info, TokenAccount>,
+    pub platform_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -68,26 +70,28 @@ pub struct WithdrawFunds<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = banking_account
+        associated_token::authority = banking_account,
+        associated_token::token_program = token_program
     )]
-    pub platform_token_account: Account<'info, TokenAccount>,
+    pub platform_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn deposit_funds(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
@@ -114,17 +118,21 @@ pub fn deposit_funds(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
     
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.platform_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, amount)?;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    // Credit what arrived, not what was sent, on transfer-fee mints
+    let received_amount = calculate_received_amount(&ctx.accounts.mint, amount)?;
     
     banking_account.balance = banking_account.balance
-        .checked_add(amount)
+        .checked_add(received_amount)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
     banking_account.last_interaction = clock.unix_timestamp;
@@ -132,7 +140,7 @@ pub fn deposit_funds(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
     emit!(FundsDeposited {
         account: banking_account.key(),
         user: ctx.accounts.user.key(),
-        amount,
+        amount: received_amount,
         new_balance: banking_account.balance,
         timestamp: clock.unix_timestamp,
     });
@@ -161,15 +169,16 @@ pub fn withdraw_funds(ctx: Context<WithdrawFunds>, amount: u64) -> Result<()> {
     
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.platform_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: banking_account.to_account_info(),
         },
         signer_seeds,
     );
     
-    transfer(transfer_ctx, amount)?;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
     
     banking_account.balance = banking_account.balance
         .checked_sub(amount)
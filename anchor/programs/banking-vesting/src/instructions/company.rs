@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, MintTo, mint_to};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, MintTo, SetAuthority, mint_to, set_authority};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::constants::*;
 use crate::events::*;
 use crate::errors::BankingVestingError;
+use crate::utils::{company_name_seed, require_signer_approval};
 
 #[derive(Accounts)]
 #[instruction(name: String)]
@@ -20,7 +23,7 @@ pub struct CreateCompany<'info> {
         init,
         payer = authority,
         space = 8 + std::mem::size_of::<Company>(),
-        seeds = [COMPANY_SEED, authority.key().as_ref(), name.as_bytes()],
+        seeds = [COMPANY_SEED, authority.key().as_ref(), &company_name_seed(&name)],
         bump
     )]
     pub company: Account<'info, Company>,
@@ -28,58 +31,230 @@ pub struct CreateCompany<'info> {
     #[account(
         init,
         payer = authority,
-        mint::decimals = 6,
+        mint::decimals = BASE_TOKEN_DECIMALS,
         mint::authority = company,
+        mint::token_program = token_program,
     )]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// Company treasury that later funds grants, only needed when minting up front
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Registers a company around a token it already issued. The program never
+/// holds mint authority over it, so the supply is whatever the mint says.
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RegisterCompanyWithMint<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Company>(),
+        seeds = [COMPANY_SEED, authority.key().as_ref(), &company_name_seed(&name)],
+        bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTransferApprovalPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MintAdditionalSupply<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized,
+        constraint = company.mint_authority_retained @ BankingVestingError::MintAuthorityRevoked
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeMintAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized,
+        constraint = company.mint_authority_retained @ BankingVestingError::MintAuthorityRevoked
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 pub fn create_company(
     ctx: Context<CreateCompany>,
     name: String,
     symbol: String,
     total_supply: u64,
+    supply_policy: SupplyPolicy,
 ) -> Result<()> {
+    let supply_cap = if supply_policy.supply_cap == 0 || !supply_policy.retain_mint_authority {
+        total_supply
+    } else {
+        supply_policy.supply_cap
+    };
+    
     require!(
-        name.len() <= MAX_COMPANY_NAME_LENGTH,
-        BankingVestingError::InvalidVestingParameters
+        supply_cap >= total_supply,
+        BankingVestingError::InvalidSupplyPolicy
     );
+    // Giving up mint authority without minting would leave the token unissuable
     require!(
-        symbol.len() <= MAX_SYMBOL_LENGTH,
-        BankingVestingError::InvalidVestingParameters
+        supply_policy.mint_to_treasury || supply_policy.retain_mint_authority,
+        BankingVestingError::InvalidSupplyPolicy
     );
     
     let company = &mut ctx.accounts.company;
     let platform = &mut ctx.accounts.platform;
     let clock = Clock::get()?;
     
-    company.authority = ctx.accounts.authority.key();
+    initialize_company(
+        company,
+        ctx.accounts.authority.key(),
+        &name,
+        &symbol,
+        ctx.accounts.mint.key(),
+        total_supply,
+        ctx.bumps.company,
+        clock.unix_timestamp,
+    )?;
     
-    // Convert strings to fixed-size arrays
-    let mut name_bytes = [0u8; 32];
-    let name_len = std::cmp::min(name.len(), 32);
-    name_bytes[..name_len].copy_from_slice(&name.as_bytes()[..name_len]);
-    company.name = name_bytes;
+    company.supply_cap = supply_cap;
+    company.mint_authority_retained = supply_policy.retain_mint_authority;
+    // Without an up-front mint the declared supply is issued later through mint_additional_supply
+    company.issued_supply = if supply_policy.mint_to_treasury { total_supply } else { 0 };
     
-    let mut symbol_bytes = [0u8; 8];
-    let symbol_len = std::cmp::min(symbol.len(), 8);
-    symbol_bytes[..symbol_len].copy_from_slice(&symbol.as_bytes()[..symbol_len]);
-    company.symbol = symbol_bytes;
+    let authority_key = ctx.accounts.authority.key();
+    let seeds = &[
+        COMPANY_SEED,
+        authority_key.as_ref(),
+        &company.name,
+        &[company.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
     
-    company.mint = ctx.accounts.mint.key();
-    company.total_supply = total_supply;
-    company.allocated_supply = 0;
-    company.employees_count = 0;
-    company.vesting_schedules_count = 0;
-    company.created_at = clock.unix_timestamp;
-    company.bump = ctx.bumps.company;
+    if supply_policy.mint_to_treasury {
+        let company_token_account = ctx.accounts.company_token_account
+            .as_ref()
+            .ok_or(BankingVestingError::AccountNotFound)?;
+        
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: company_token_account.to_account_info(),
+                authority: company.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        mint_to(mint_ctx, total_supply)?;
+        
+        emit!(CompanySupplyMinted {
+            company: company.key(),
+            mint: ctx.accounts.mint.key(),
+            treasury: company_token_account.key(),
+            amount: total_supply,
+            total_supply,
+            issued_supply: company.issued_supply,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    if !supply_policy.retain_mint_authority {
+        let set_authority_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: company.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        set_authority(set_authority_ctx, AuthorityType::MintTokens, None)?;
+        
+        emit!(CompanyMintAuthorityRevoked {
+            company: company.key(),
+            mint: ctx.accounts.mint.key(),
+            total_supply,
+            issued_supply: company.issued_supply,
+            timestamp: clock.unix_timestamp,
+        });
+    }
     
     platform.total_companies = platform.total_companies
         .checked_add(1)
@@ -96,3 +271,227 @@ pub fn create_company(
     
     Ok(())
 }
+
+pub fn register_company_with_mint(
+    ctx: Context<RegisterCompanyWithMint>,
+    name: String,
+    symbol: String,
+) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let platform = &mut ctx.accounts.platform;
+    let mint = &ctx.accounts.mint;
+    let clock = Clock::get()?;
+    
+    initialize_company(
+        company,
+        ctx.accounts.authority.key(),
+        &name,
+        &symbol,
+        mint.key(),
+        mint.supply,
+        ctx.bumps.company,
+        clock.unix_timestamp,
+    )?;
+    
+    platform.total_companies = platform.total_companies
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(CompanyCreated {
+        company: company.key(),
+        authority: ctx.accounts.authority.key(),
+        name,
+        mint: mint.key(),
+        total_supply: mint.supply,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn set_transfer_approval_policy(
+    ctx: Context<SetTransferApprovalPolicy>,
+    require_approval: bool,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    company.require_transfer_approval = require_approval;
+    
+    emit!(TransferApprovalPolicyUpdated {
+        company: company.key(),
+        require_approval,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Issues more of the company token into its treasury, never past the supply
+/// cap fixed at creation
+pub fn mint_additional_supply(ctx: Context<MintAdditionalSupply>, amount: u64) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    require!(amount > 0, BankingVestingError::InvalidAmount);
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    let issued_supply = company.issued_supply
+        .checked_add(amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        issued_supply <= company.supply_cap,
+        BankingVestingError::SupplyCapExceeded
+    );
+    
+    let company_creator = company.creator;
+    let company_name = company.name;
+    let seeds = &[
+        COMPANY_SEED,
+        company_creator.as_ref(),
+        &company_name,
+        &[company.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.company_token_account.to_account_info(),
+            authority: company.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    mint_to(mint_ctx, amount)?;
+    
+    company.issued_supply = issued_supply;
+    // Issuing past the declared supply grows it, up to the cap
+    company.total_supply = company.total_supply.max(issued_supply);
+    
+    emit!(CompanySupplyMinted {
+        company: company.key(),
+        mint: company.mint,
+        treasury: ctx.accounts.company_token_account.key(),
+        amount,
+        total_supply: company.total_supply,
+        issued_supply,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Permanently gives up the company's mint authority, freezing the supply
+pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    let company_creator = company.creator;
+    let company_name = company.name;
+    let seeds = &[
+        COMPANY_SEED,
+        company_creator.as_ref(),
+        &company_name,
+        &[company.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let set_authority_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        SetAuthority {
+            current_authority: company.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    set_authority(set_authority_ctx, AuthorityType::MintTokens, None)?;
+    
+    // Whatever was declared but never minted can no longer be issued
+    company.mint_authority_retained = false;
+    company.supply_cap = company.issued_supply;
+    company.total_supply = company.issued_supply;
+    
+    emit!(CompanyMintAuthorityRevoked {
+        company: company.key(),
+        mint: company.mint,
+        total_supply: company.total_supply,
+        issued_supply: company.issued_supply,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn initialize_company(
+    company: &mut Company,
+    authority: Pubkey,
+    name: &str,
+    symbol: &str,
+    mint: Pubkey,
+    total_supply: u64,
+    bump: u8,
+    created_at: i64,
+) -> Result<()> {
+    require!(
+        name.len() <= MAX_COMPANY_NAME_LENGTH,
+        BankingVestingError::InvalidVestingParameters
+    );
+    require!(
+        symbol.len() <= MAX_SYMBOL_LENGTH,
+        BankingVestingError::InvalidVestingParameters
+    );
+    
+    company.authority = authority;
+    company.creator = authority;
+    company.pending_authority = Pubkey::default();
+    
+    // Convert strings to fixed-size arrays
+    company.name = company_name_seed(name);
+    
+    let mut symbol_bytes = [0u8; 8];
+    let symbol_len = std::cmp::min(symbol.len(), 8);
+    symbol_bytes[..symbol_len].copy_from_slice(&symbol.as_bytes()[..symbol_len]);
+    company.symbol = symbol_bytes;
+    
+    company.mint = mint;
+    company.total_supply = total_supply;
+    company.issued_supply = total_supply;
+    company.allocated_supply = 0;
+    company.employees_count = 0;
+    company.vesting_schedules_count = 0;
+    company.require_transfer_approval = false;
+    company.change_of_control_at = 0;
+    company.withholding = WithholdingTerms::default();
+    company.supply_cap = total_supply;
+    company.mint_authority_retained = false;
+    company.signer_set = [Pubkey::default(); MAX_COMPANY_SIGNERS];
+    company.signer_count = 0;
+    company.approval_threshold = 0;
+    company.grant_approval_threshold = 0;
+    company.created_at = created_at;
+    company.bump = bump;
+    
+    Ok(())
+}
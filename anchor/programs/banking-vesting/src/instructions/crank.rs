@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+use super::vesting::{transfer_from_vesting_vault, transfer_withholding};
+
+#[derive(Accounts)]
+pub struct SetClaimSettings<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            vesting_schedule.company.as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankClaimVestedTokens<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.permissionless_claims
+            || cranker.key() == vesting_schedule.claim_delegate
+            || cranker.key() == vesting_schedule.beneficiary @ BankingVestingError::Unauthorized,
+        constraint = vesting_schedule.option_terms.payment_mint == Pubkey::default() @ BankingVestingError::OptionExerciseRequired
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    /// Payout always lands in the beneficiary's ATA, whoever triggers the claim
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Beneficiary address, validated against the schedule
+    #[account(
+        constraint = beneficiary.key() == vesting_schedule.beneficiary @ BankingVestingError::Unauthorized
+    )]
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    /// Receives the tip, only needed when the schedule pays one
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = cranker,
+        token::token_program = token_program
+    )]
+    pub cranker_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Receives the withheld share, only needed when withholding applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub withholding_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn set_claim_settings(
+    ctx: Context<SetClaimSettings>,
+    claim_delegate: Pubkey,
+    permissionless_claims: bool,
+    crank_tip_bps: u16,
+) -> Result<()> {
+    require!(
+        crank_tip_bps <= MAX_CRANK_TIP_BPS,
+        BankingVestingError::InvalidCrankTip
+    );
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    vesting_schedule.claim_delegate = claim_delegate;
+    vesting_schedule.permissionless_claims = permissionless_claims;
+    vesting_schedule.crank_tip_bps = crank_tip_bps;
+    
+    emit!(ClaimSettingsUpdated {
+        schedule: vesting_schedule.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        claim_delegate,
+        permissionless_claims,
+        crank_tip_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Claims on the beneficiary's behalf. Callable by the beneficiary, their
+/// delegate, or anyone once the schedule opts into permissionless claims;
+/// keepers other than the beneficiary earn the tip the beneficiary agreed to.
+pub fn crank_claim_vested_tokens(ctx: Context<CrankClaimVestedTokens>) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let claimable_amount = calculate_claimable_amount(vesting_schedule, clock.unix_timestamp)?;
+    
+    require!(
+        claimable_amount > 0,
+        BankingVestingError::NoTokensAvailable
+    );
+    
+    let cranker_key = ctx.accounts.cranker.key();
+    let tip = if cranker_key == vesting_schedule.beneficiary {
+        0
+    } else {
+        (claimable_amount as u128)
+            .checked_mul(vesting_schedule.crank_tip_bps as u128)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?
+            .checked_div(BASIS_POINTS as u128)
+            .ok_or(BankingVestingError::ArithmeticOverflow)? as u64
+    };
+    
+    let withholding = vesting_schedule.withholding;
+    let withheld_amount = transfer_withholding(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.withholding_token_account.as_ref(),
+        ctx.accounts.token_program.to_account_info(),
+        &withholding,
+        claimable_amount,
+    )?;
+    
+    // The tip comes out of the beneficiary's share, never the withheld one
+    let payout = claimable_amount
+        .checked_sub(withheld_amount)
+        .and_then(|net_amount| net_amount.checked_sub(tip))
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.beneficiary_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        payout,
+    )?;
+    
+    if tip > 0 {
+        let cranker_token_account = ctx.accounts.cranker_token_account
+            .as_ref()
+            .ok_or(BankingVestingError::AccountNotFound)?;
+        
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            &ctx.accounts.mint,
+            ctx.accounts.vesting_vault.to_account_info(),
+            cranker_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            tip,
+        )?;
+    }
+    
+    vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+        .checked_add(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.last_claimed = clock.unix_timestamp;
+    
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(TokensClaimed {
+        schedule: vesting_schedule.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        amount: payout,
+        withheld_amount,
+        withholding_account: withholding.destination,
+        total_claimed: vesting_schedule.claimed_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    emit!(ClaimCranked {
+        schedule: vesting_schedule.key(),
+        cranker: cranker_key,
+        amount: claimable_amount,
+        tip,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,462 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::merkle::{DistributorLeaf, verify_merkle_proof};
+use crate::utils::*;
+
+#[derive(Accounts)]
+#[instruction(distributor_id: u64)]
+pub struct CreateMerkleDistributor<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<MerkleDistributor>(),
+        seeds = [DISTRIBUTOR_SEED, company.key().as_ref(), &distributor_id.to_le_bytes()],
+        bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+    
+    #[account(
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackDistributor<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [DISTRIBUTOR_SEED, company.key().as_ref(), &distributor.distributor_id.to_le_bytes()],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+    
+    #[account(
+        constraint = mint.key() == distributor.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFromDistributor<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [DISTRIBUTOR_SEED, company.key().as_ref(), &distributor.distributor_id.to_le_bytes()],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+    
+    /// Lazily materialized grant, created by the recipient's first claim
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = 8 + std::mem::size_of::<DistributorClaim>(),
+        seeds = [DISTRIBUTOR_CLAIM_SEED, distributor.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub distributor_claim: Account<'info, DistributorClaim>,
+    
+    #[account(
+        constraint = mint.key() == distributor.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program
+    )]
+    pub distributor_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_merkle_distributor(
+    ctx: Context<CreateMerkleDistributor>,
+    distributor_id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        total_amount > 0,
+        BankingVestingError::InvalidAmount
+    );
+    
+    let clock = Clock::get()?;
+    
+    // Should sit after the last leaf finishes vesting, claims stop here
+    require!(
+        expires_at > clock.unix_timestamp,
+        BankingVestingError::InvalidTimestamp
+    );
+    
+    require_grant_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        total_amount,
+        ctx.remaining_accounts,
+    )?;
+    
+    // Transfer-fee mints are grossed up so the vault can cover every leaf
+    let funding_amount = calculate_gross_transfer_amount(&ctx.accounts.mint, total_amount)?;
+    
+    require!(
+        ctx.accounts.company_token_account.amount >= funding_amount,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    let distributor = &mut ctx.accounts.distributor;
+    let company = &mut ctx.accounts.company;
+    
+    distributor.company = company.key();
+    distributor.mint = ctx.accounts.mint.key();
+    distributor.distributor_id = distributor_id;
+    distributor.merkle_root = merkle_root;
+    distributor.total_amount = total_amount;
+    distributor.total_claimed = 0;
+    distributor.recipients_claimed = 0;
+    distributor.expires_at = expires_at;
+    distributor.created_at = clock.unix_timestamp;
+    distributor.bump = ctx.bumps.distributor;
+    
+    // Fund the whole pool up front, same guarantee as individual schedules
+    let company_creator = company.creator;
+    let company_name = company.name;
+    let seeds = &[
+        COMPANY_SEED,
+        company_creator.as_ref(),
+        &company_name,
+        &[company.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.company_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.distributor_vault.to_account_info(),
+            authority: company.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, funding_amount, ctx.accounts.mint.decimals)?;
+    
+    company.allocated_supply = company.allocated_supply
+        .checked_add(total_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(MerkleDistributorCreated {
+        distributor: distributor.key(),
+        company: company.key(),
+        distributor_id,
+        merkle_root,
+        total_amount,
+        expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Returns everything recipients did not claim before the distributor expired
+/// and releases it from the company's allocated supply
+pub fn clawback_distributor(ctx: Context<ClawbackDistributor>) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let distributor = &mut ctx.accounts.distributor;
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.unix_timestamp >= distributor.expires_at,
+        BankingVestingError::DistributorNotExpired
+    );
+    
+    let unclaimed_amount = distributor.total_amount
+        .checked_sub(distributor.total_claimed)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        unclaimed_amount > 0,
+        BankingVestingError::NoTokensAvailable
+    );
+    
+    let company_key = distributor.company;
+    let distributor_id_bytes = distributor.distributor_id.to_le_bytes();
+    let seeds = &[
+        DISTRIBUTOR_SEED,
+        company_key.as_ref(),
+        &distributor_id_bytes,
+        &[distributor.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.distributor_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.company_token_account.to_account_info(),
+            authority: distributor.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, unclaimed_amount, ctx.accounts.mint.decimals)?;
+    
+    // Nothing is left to claim, total_claimed stays as the final tally
+    distributor.total_amount = distributor.total_claimed;
+    
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(unclaimed_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(DistributorClawedBack {
+        distributor: distributor.key(),
+        company: company.key(),
+        amount: unclaimed_amount,
+        total_claimed: distributor.total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn claim_from_distributor(
+    ctx: Context<ClaimFromDistributor>,
+    leaf: DistributorLeaf,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require_keys_eq!(
+        leaf.beneficiary,
+        ctx.accounts.beneficiary.key(),
+        BankingVestingError::Unauthorized
+    );
+    
+    require!(
+        verify_merkle_proof(&proof, &ctx.accounts.distributor.merkle_root, leaf.hash()),
+        BankingVestingError::InvalidMerkleProof
+    );
+    
+    let distributor_claim = &mut ctx.accounts.distributor_claim;
+    let distributor = &mut ctx.accounts.distributor;
+    let clock = Clock::get()?;
+    
+    require!(
+        clock.unix_timestamp < distributor.expires_at,
+        BankingVestingError::DistributorExpired
+    );
+    
+    let is_new_claim = distributor_claim.distributor == Pubkey::default();
+    
+    if is_new_claim {
+        distributor_claim.distributor = distributor.key();
+        distributor_claim.beneficiary = leaf.beneficiary;
+        distributor_claim.total_amount = leaf.amount;
+        distributor_claim.claimed_amount = 0;
+        distributor_claim.start_time = leaf.start_time;
+        distributor_claim.cliff_duration = leaf.cliff_duration;
+        distributor_claim.vesting_duration = leaf.vesting_duration;
+        distributor_claim.last_claimed = 0;
+        distributor_claim.bump = ctx.bumps.distributor_claim;
+        
+        distributor.recipients_claimed = distributor.recipients_claimed
+            .checked_add(1)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        emit!(MerkleGrantMaterialized {
+            distributor: distributor.key(),
+            claim: distributor_claim.key(),
+            beneficiary: leaf.beneficiary,
+            total_amount: leaf.amount,
+            timestamp: clock.unix_timestamp,
+        });
+    } else {
+        // A root listing the same recipient twice must not let them mix entries
+        require!(
+            distributor_claim.total_amount == leaf.amount
+                && distributor_claim.start_time == leaf.start_time
+                && distributor_claim.cliff_duration == leaf.cliff_duration
+                && distributor_claim.vesting_duration == leaf.vesting_duration,
+            BankingVestingError::InvalidMerkleProof
+        );
+    }
+    
+    let vested_amount = calculate_vested_amount(
+        distributor_claim.total_amount,
+        distributor_claim.start_time,
+        distributor_claim.cliff_duration,
+        distributor_claim.vesting_duration,
+        clock.unix_timestamp,
+    )?;
+    
+    let claimable_amount = vested_amount
+        .checked_sub(distributor_claim.claimed_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // The first call may only materialize the grant, later calls must pay out
+    if claimable_amount == 0 {
+        require!(is_new_claim, BankingVestingError::NoTokensAvailable);
+        return Ok(());
+    }
+    
+    distributor.total_claimed = distributor.total_claimed
+        .checked_add(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        distributor.total_claimed <= distributor.total_amount,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    let company_key = distributor.company;
+    let distributor_id_bytes = distributor.distributor_id.to_le_bytes();
+    let seeds = &[
+        DISTRIBUTOR_SEED,
+        company_key.as_ref(),
+        &distributor_id_bytes,
+        &[distributor.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.distributor_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: distributor.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, claimable_amount, ctx.accounts.mint.decimals)?;
+    
+    distributor_claim.claimed_amount = distributor_claim.claimed_amount
+        .checked_add(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    distributor_claim.last_claimed = clock.unix_timestamp;
+    
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(TokensClaimed {
+        schedule: distributor_claim.key(),
+        beneficiary: leaf.beneficiary,
+        amount: claimable_amount,
+        withheld_amount: 0,
+        withholding_account: Pubkey::default(),
+        total_claimed: distributor_claim.claimed_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
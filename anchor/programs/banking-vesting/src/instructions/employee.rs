@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+use super::vesting::settle_revocation;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct OnboardEmployee<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Employee>(),
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub employee: Account<'info, Employee>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEmployee<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.status != EmployeeStatus::Terminated @ BankingVestingError::EmployeeTerminated
+    )]
+    pub employee: Account<'info, Employee>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LinkEmployeeSchedule<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.status != EmployeeStatus::Terminated @ BankingVestingError::EmployeeTerminated
+    )]
+    pub employee: Account<'info, Employee>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            employee.wallet.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked,
+        constraint = vesting_schedule.employee == Pubkey::default() @ BankingVestingError::ScheduleAlreadyLinked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OffboardEmployee<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.status != EmployeeStatus::Terminated @ BankingVestingError::EmployeeTerminated
+    )]
+    pub employee: Account<'info, Employee>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Applies a terminated employee's revocation policy to one linked schedule.
+/// The company already decided the outcome at offboarding, so anyone can run it.
+#[derive(Accounts)]
+pub struct RevokeTerminatedSchedule<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), beneficiary.key().as_ref()],
+        bump = employee.bump,
+        constraint = employee.status == EmployeeStatus::Terminated
+            && employee.leaver_policy.is_some() @ BankingVestingError::EmployeeNotTerminated
+    )]
+    pub employee: Account<'info, Employee>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.employee == employee.key() @ BankingVestingError::Unauthorized,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    /// Required when part of the escrow is staked, the principal is pulled back first
+    #[account(
+        mut,
+        seeds = [STAKING_SEED, mint.key().as_ref()],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Employee wallet, validated through the employee and schedule seeds
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    pub cranker: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn onboard_employee(
+    ctx: Context<OnboardEmployee>,
+    wallet: Pubkey,
+    department: String,
+    role: String,
+    hire_date: i64,
+) -> Result<()> {
+    require!(
+        wallet != Pubkey::default()
+            && department.len() <= MAX_DEPARTMENT_LENGTH
+            && role.len() <= MAX_ROLE_LENGTH
+            && hire_date > 0,
+        BankingVestingError::InvalidEmployeeDetails
+    );
+    
+    let company = &mut ctx.accounts.company;
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+    
+    employee.company = company.key();
+    employee.wallet = wallet;
+    employee.department = to_label_bytes(&department);
+    employee.role = to_label_bytes(&role);
+    employee.status = EmployeeStatus::Active;
+    employee.hire_date = hire_date;
+    employee.termination_date = 0;
+    employee.leaver_policy = None;
+    employee.schedules_linked = 0;
+    employee.schedules_revoked = 0;
+    employee.created_at = clock.unix_timestamp;
+    employee.updated_at = clock.unix_timestamp;
+    employee.bump = ctx.bumps.employee;
+    
+    company.employees_count = company.employees_count
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(EmployeeOnboarded {
+        employee: employee.key(),
+        company: company.key(),
+        wallet,
+        department,
+        role,
+        hire_date,
+        employees_count: company.employees_count,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Changes department, role or leave status. Termination goes through
+/// `offboard_employee` so it is never applied by accident.
+pub fn update_employee(
+    ctx: Context<UpdateEmployee>,
+    department: String,
+    role: String,
+    status: EmployeeStatus,
+) -> Result<()> {
+    require!(
+        department.len() <= MAX_DEPARTMENT_LENGTH
+            && role.len() <= MAX_ROLE_LENGTH
+            && status != EmployeeStatus::Terminated,
+        BankingVestingError::InvalidEmployeeDetails
+    );
+    
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+    
+    employee.department = to_label_bytes(&department);
+    employee.role = to_label_bytes(&role);
+    employee.status = status;
+    employee.updated_at = clock.unix_timestamp;
+    
+    emit!(EmployeeUpdated {
+        employee: employee.key(),
+        company: employee.company,
+        department,
+        role,
+        status: employee_status_str(status).to_string(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn link_employee_schedule(ctx: Context<LinkEmployeeSchedule>) -> Result<()> {
+    let employee = &mut ctx.accounts.employee;
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    vesting_schedule.employee = employee.key();
+    
+    employee.schedules_linked = employee.schedules_linked
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(EmployeeScheduleLinked {
+        employee: employee.key(),
+        schedule: vesting_schedule.key(),
+        schedules_linked: employee.schedules_linked,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Marks the employee terminated as of `termination_date`. With a leaver
+/// policy every linked schedule becomes revocable through
+/// `revoke_terminated_schedule` under that policy, without one the schedules
+/// are left for the company to handle individually.
+pub fn offboard_employee(
+    ctx: Context<OffboardEmployee>,
+    termination_date: i64,
+    leaver_policy: Option<LeaverPolicy>,
+) -> Result<()> {
+    // Choosing a policy here is what revokes the linked schedules
+    if leaver_policy.is_some() {
+        require_signer_approval(
+            &ctx.accounts.company,
+            &ctx.accounts.authority.key(),
+            ctx.remaining_accounts,
+        )?;
+    }
+    
+    let company = &mut ctx.accounts.company;
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+    
+    require!(
+        termination_date >= employee.hire_date && termination_date <= clock.unix_timestamp,
+        BankingVestingError::InvalidTimestamp
+    );
+    
+    employee.status = EmployeeStatus::Terminated;
+    employee.termination_date = termination_date;
+    employee.leaver_policy = leaver_policy;
+    employee.updated_at = clock.unix_timestamp;
+    
+    company.employees_count = company.employees_count
+        .checked_sub(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(EmployeeOffboarded {
+        employee: employee.key(),
+        company: company.key(),
+        wallet: employee.wallet,
+        termination_date,
+        leaver_policy: leaver_policy.map(|policy| leaver_policy_str(policy).to_string()),
+        schedules_linked: employee.schedules_linked,
+        employees_count: company.employees_count,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_terminated_schedule(ctx: Context<RevokeTerminatedSchedule>) -> Result<()> {
+    // Anyone can call this, so they can't skip the metric to settle on a stale unlock
+    require_performance_metric(
+        &ctx.accounts.vesting_schedule,
+        ctx.accounts.performance_metric.as_ref(),
+    )?;
+    
+    let accounts = ctx.accounts;
+    let employee = &mut accounts.employee;
+    
+    let leaver_policy = employee.leaver_policy
+        .ok_or(BankingVestingError::EmployeeNotTerminated)?;
+    
+    settle_revocation(
+        &mut accounts.vesting_schedule,
+        &mut accounts.company,
+        &accounts.mint,
+        &accounts.vesting_vault,
+        &accounts.company_token_account,
+        &accounts.beneficiary_token_account,
+        accounts.performance_metric.as_ref(),
+        accounts.staking_pool.as_mut(),
+        accounts.pool_token_account.as_ref(),
+        &accounts.token_program,
+        employee.termination_date,
+        leaver_policy,
+    )?;
+    
+    employee.schedules_revoked = employee.schedules_revoked
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
+
+fn to_label_bytes(value: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let len = std::cmp::min(value.len(), 32);
+    bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+    bytes
+}
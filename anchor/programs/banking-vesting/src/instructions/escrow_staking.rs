@@ -0,0 +1,367 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+use super::vesting::transfer_from_vesting_vault;
+
+#[derive(Accounts)]
+pub struct StakeVestingEscrow<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            vesting_schedule.company.as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        seeds = [STAKING_SEED, mint.key().as_ref()],
+        bump = staking_pool.bump,
+        constraint = staking_pool.is_active @ BankingVestingError::StakingPoolInactive
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeVestingEscrow<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            vesting_schedule.company.as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        seeds = [STAKING_SEED, mint.key().as_ref()],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakeRewards<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            vesting_schedule.company.as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        seeds = [STAKING_SEED, mint.key().as_ref()],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Rewards are the beneficiary's right away, unlike the staked principal
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Moves still-unvested escrow into the pool for the schedule's mint. The stake
+/// is held by the pool PDA on the schedule's behalf and only ever returns to
+/// the schedule's vault.
+pub fn stake_vesting_escrow(ctx: Context<StakeVestingEscrow>, amount: u64) -> Result<()> {
+    require!(
+        amount >= scale_to_decimals(MIN_STAKE_AMOUNT, ctx.accounts.mint.decimals)?,
+        BankingVestingError::InvalidAmount
+    );
+    // Principal has to round-trip intact, a transfer fee would eat into the grant
+    require!(
+        !has_transfer_fee(&ctx.accounts.mint)?,
+        BankingVestingError::TransferFeeNotSupported
+    );
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let clock = Clock::get()?;
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let vested_amount = calculate_schedule_vested_amount(vesting_schedule, clock.unix_timestamp)?;
+    let unvested_amount = vesting_schedule.total_amount
+        .checked_sub(vested_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    let staked_amount = vesting_schedule.staked_amount
+        .checked_add(amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // Vested tokens have to stay in the vault so claims keep working
+    require!(
+        staked_amount <= unvested_amount,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    accrue_stake_rewards(vesting_schedule, staking_pool.apy_rate, clock.unix_timestamp)?;
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.pool_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+    
+    vesting_schedule.staked_amount = staked_amount;
+    
+    staking_pool.total_staked = staking_pool.total_staked
+        .checked_add(amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(VestingEscrowStaked {
+        schedule: vesting_schedule.key(),
+        pool: staking_pool.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        amount,
+        staked_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn unstake_vesting_escrow(ctx: Context<UnstakeVestingEscrow>, amount: u64) -> Result<()> {
+    require!(amount > 0, BankingVestingError::InvalidAmount);
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let clock = Clock::get()?;
+    
+    require!(
+        vesting_schedule.staked_amount >= amount,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    accrue_stake_rewards(vesting_schedule, staking_pool.apy_rate, clock.unix_timestamp)?;
+    
+    transfer_from_staking_pool(
+        staking_pool,
+        &ctx.accounts.mint,
+        ctx.accounts.pool_token_account.to_account_info(),
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+    
+    vesting_schedule.staked_amount = vesting_schedule.staked_amount
+        .checked_sub(amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    staking_pool.total_staked = staking_pool.total_staked
+        .checked_sub(amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(VestingEscrowUnstaked {
+        schedule: vesting_schedule.key(),
+        pool: staking_pool.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        amount,
+        staked_amount: vesting_schedule.staked_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn claim_stake_rewards(ctx: Context<ClaimStakeRewards>) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let staking_pool = &mut ctx.accounts.staking_pool;
+    let clock = Clock::get()?;
+    
+    accrue_stake_rewards(vesting_schedule, staking_pool.apy_rate, clock.unix_timestamp)?;
+    
+    let rewards = vesting_schedule.stake_rewards_accrued;
+    
+    require!(rewards > 0, BankingVestingError::NoTokensAvailable);
+    
+    // Rewards must come out of the pool's surplus, never other stakers' principal
+    let required_balance = staking_pool.total_staked
+        .checked_add(rewards)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        ctx.accounts.pool_token_account.amount >= required_balance,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    transfer_from_staking_pool(
+        staking_pool,
+        &ctx.accounts.mint,
+        ctx.accounts.pool_token_account.to_account_info(),
+        ctx.accounts.beneficiary_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        rewards,
+    )?;
+    
+    vesting_schedule.stake_rewards_accrued = 0;
+    
+    staking_pool.total_rewards = staking_pool.total_rewards
+        .checked_add(rewards)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(StakeRewardsClaimed {
+        schedule: vesting_schedule.key(),
+        pool: staking_pool.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        rewards,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Moves `amount` out of a staking pool's token account, signing as the pool PDA
+pub fn transfer_from_staking_pool<'info>(
+    staking_pool: &Account<'info, StakingPool>,
+    mint: &InterfaceAccount<'info, Mint>,
+    pool_token_account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        STAKING_SEED,
+        staking_pool.mint.as_ref(),
+        &[staking_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        TransferChecked {
+            from: pool_token_account,
+            mint: mint.to_account_info(),
+            to: destination,
+            authority: staking_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, amount, mint.decimals)
+}
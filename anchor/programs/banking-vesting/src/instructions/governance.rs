@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct ProposeCompanyAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCompanyAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.pending_authority != Pubkey::default()
+            && company.pending_authority == new_authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCompanySigners<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// First step of an authority rotation, nothing changes until the new key
+/// accepts. Proposing the default key cancels a pending transfer.
+pub fn propose_company_authority(
+    ctx: Context<ProposeCompanyAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    require!(
+        new_authority != company.authority,
+        BankingVestingError::Unauthorized
+    );
+    
+    company.pending_authority = new_authority;
+    
+    emit!(CompanyAuthorityProposed {
+        company: company.key(),
+        authority: company.authority,
+        pending_authority: new_authority,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn accept_company_authority(ctx: Context<AcceptCompanyAuthority>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    let previous_authority = company.authority;
+    
+    company.authority = company.pending_authority;
+    company.pending_authority = Pubkey::default();
+    
+    emit!(CompanyAuthorityTransferred {
+        company: company.key(),
+        previous_authority,
+        new_authority: company.authority,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Replaces the company's M-of-N signer set. Once a set is in place, changing
+/// it needs that set's approval like any other sensitive action.
+pub fn set_company_signers(
+    ctx: Context<SetCompanySigners>,
+    signers: Vec<Pubkey>,
+    approval_threshold: u8,
+    grant_approval_threshold: u64,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    require!(
+        signers.len() <= MAX_COMPANY_SIGNERS
+            && (approval_threshold as usize) <= signers.len()
+            && (approval_threshold > 0 || signers.is_empty()),
+        BankingVestingError::InvalidSignerSet
+    );
+    
+    for (index, signer) in signers.iter().enumerate() {
+        require!(
+            *signer != Pubkey::default() && !signers[..index].contains(signer),
+            BankingVestingError::InvalidSignerSet
+        );
+    }
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    let mut signer_set = [Pubkey::default(); MAX_COMPANY_SIGNERS];
+    signer_set[..signers.len()].copy_from_slice(&signers);
+    
+    company.signer_set = signer_set;
+    company.signer_count = signers.len() as u8;
+    company.approval_threshold = approval_threshold;
+    company.grant_approval_threshold = grant_approval_threshold;
+    
+    emit!(CompanySignersUpdated {
+        company: company.key(),
+        signers,
+        approval_threshold,
+        grant_approval_threshold,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::require_signer_approval;
+
+#[derive(Accounts)]
+pub struct PauseVestingSchedule<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked,
+        constraint = vesting_schedule.paused_at == 0 @ BankingVestingError::ScheduleAlreadyPaused
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResumeVestingSchedule<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.paused_at != 0 @ BankingVestingError::ScheduleNotPaused
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Suspends vesting for a single schedule, unlike `emergency_pause` this
+/// leaves the rest of the platform and already vested tokens untouched
+pub fn pause_vesting_schedule(ctx: Context<PauseVestingSchedule>) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    vesting_schedule.paused_at = clock.unix_timestamp;
+    
+    emit!(VestingSchedulePaused {
+        schedule: vesting_schedule.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        paused_at: clock.unix_timestamp,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn resume_vesting_schedule(ctx: Context<ResumeVestingSchedule>) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    let paused_duration = clock.unix_timestamp
+        .checked_sub(vesting_schedule.paused_at)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // The leave is added to the schedule so the end date moves out by the same amount
+    vesting_schedule.total_paused_duration = vesting_schedule.total_paused_duration
+        .checked_add(paused_duration)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.paused_at = 0;
+    
+    emit!(VestingScheduleResumed {
+        schedule: vesting_schedule.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        paused_duration,
+        total_paused_duration: vesting_schedule.total_paused_duration,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
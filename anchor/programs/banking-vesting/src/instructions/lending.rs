@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::constants::*;
@@ -33,27 +33,29 @@ pub struct CreateLoanRequest<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = borrower
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program
     )]
-    pub borrower_token_account: Account<'info, TokenAccount>,
+    pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = borrower,
         associated_token::mint = mint,
-        associated_token::authority = loan_request
+        associated_token::authority = loan_request,
+        associated_token::token_program = token_program
     )]
-    pub collateral_account: Account<'info, TokenAccount>,
+    pub collateral_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub borrower: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -76,26 +78,26 @@ pub struct ApproveLoan<'info> {
     )]
     pub loan_request: Account<'info, LoanRequest>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = loan_request.borrower
     )]
-    pub borrower_token_account: Account<'info, TokenAccount>,
+    pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = platform.treasury
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -116,33 +118,35 @@ pub struct RepayLoan<'info> {
     )]
     pub loan_request: Account<'info, LoanRequest>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = borrower
+        associated_token::authority = borrower,
+        associated_token::token_program = token_program
     )]
-    pub borrower_token_account: Account<'info, TokenAccount>,
+    pub borrower_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = platform.treasury
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = loan_request
+        associated_token::authority = loan_request,
+        associated_token::token_program = token_program
     )]
-    pub collateral_account: Account<'info, TokenAccount>,
+    pub collateral_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub borrower: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -162,26 +166,28 @@ pub struct LiquidatePosition<'info> {
     )]
     pub loan_request: Account<'info, LoanRequest>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = liquidator
+        associated_token::authority = liquidator,
+        associated_token::token_program = token_program
     )]
-    pub liquidator_token_account: Account<'info, TokenAccount>,
+    pub liquidator_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = loan_request
+        associated_token::authority = loan_request,
+        associated_token::token_program = token_program
     )]
-    pub collateral_account: Account<'info, TokenAccount>,
+    pub collateral_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub liquidator: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn create_loan_request(
@@ -190,7 +196,10 @@ pub fn create_loan_request(
     duration: i64, 
     collateral_amount: u64
 ) -> Result<()> {
-    validate_loan_parameters(amount, collateral_amount, duration)?;
+    // Only what actually lands in escrow counts as collateral on transfer-fee mints
+    let collateral_received = calculate_received_amount(&ctx.accounts.mint, collateral_amount)?;
+    
+    validate_loan_parameters(amount, collateral_received, duration, ctx.accounts.mint.decimals)?;
     
     require!(
         ctx.accounts.borrower_token_account.amount >= collateral_amount,
@@ -203,20 +212,21 @@ pub fn create_loan_request(
     // Transfer collateral to escrow
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.borrower_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.collateral_account.to_account_info(),
             authority: ctx.accounts.borrower.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, collateral_amount)?;
+    transfer_checked(transfer_ctx, collateral_amount, ctx.accounts.mint.decimals)?;
     
     loan_request.borrower = ctx.accounts.borrower.key();
     loan_request.mint = ctx.accounts.mint.key();
     loan_request.amount = amount;
-    loan_request.collateral_amount = collateral_amount;
-    loan_request.interest_rate = calculate_interest_rate(amount, collateral_amount)?;
+    loan_request.collateral_amount = collateral_received;
+    loan_request.interest_rate = calculate_interest_rate(amount, collateral_received)?;
     loan_request.duration = duration;
     loan_request.start_time = 0; // Set when approved
     loan_request.status = LoanStatus::Pending;
@@ -228,7 +238,7 @@ pub fn create_loan_request(
         loan: loan_request.key(),
         borrower: ctx.accounts.borrower.key(),
         amount,
-        collateral_amount,
+        collateral_amount: collateral_received,
         duration,
         timestamp: clock.unix_timestamp,
     });
@@ -243,14 +253,15 @@ pub fn approve_loan(ctx: Context<ApproveLoan>) -> Result<()> {
     // Transfer loan amount to borrower
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.treasury_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.borrower_token_account.to_account_info(),
             authority: ctx.accounts.authority.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, loan_request.amount)?;
+    transfer_checked(transfer_ctx, loan_request.amount, ctx.accounts.mint.decimals)?;
     
     loan_request.status = LoanStatus::Active;
     loan_request.start_time = clock.unix_timestamp;
@@ -281,23 +292,26 @@ pub fn repay_loan(ctx: Context<RepayLoan>, amount: u64) -> Result<()> {
     
     let total_debt = loan_request.amount + interest - loan_request.repaid_amount;
     let repayment_amount = std::cmp::min(amount, total_debt);
+    // The borrower covers any transfer fee so the treasury is credited in full
+    let payment_amount = calculate_gross_transfer_amount(&ctx.accounts.mint, repayment_amount)?;
     
     require!(
-        ctx.accounts.borrower_token_account.amount >= repayment_amount,
+        ctx.accounts.borrower_token_account.amount >= payment_amount,
         BankingVestingError::InsufficientBalance
     );
     
     // Transfer repayment to treasury
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.borrower_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.treasury_token_account.to_account_info(),
             authority: ctx.accounts.borrower.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, repayment_amount)?;
+    transfer_checked(transfer_ctx, payment_amount, ctx.accounts.mint.decimals)?;
     
     loan_request.repaid_amount = loan_request.repaid_amount
         .checked_add(repayment_amount)
@@ -317,15 +331,16 @@ pub fn repay_loan(ctx: Context<RepayLoan>, amount: u64) -> Result<()> {
         
         let return_collateral_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.collateral_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.borrower_token_account.to_account_info(),
                 authority: loan_request.to_account_info(),
             },
             signer_seeds,
         );
         
-        transfer(return_collateral_ctx, loan_request.collateral_amount)?;
+        transfer_checked(return_collateral_ctx, loan_request.collateral_amount, ctx.accounts.mint.decimals)?;
         loan_request.status = LoanStatus::Repaid;
     }
     
@@ -378,15 +393,16 @@ pub fn liquidate_position(ctx: Context<LiquidatePosition>) -> Result<()> {
     
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.collateral_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.liquidator_token_account.to_account_info(),
             authority: loan_request.to_account_info(),
         },
         signer_seeds,
     );
     
-    transfer(transfer_ctx, loan_request.collateral_amount)?;
+    transfer_checked(transfer_ctx, loan_request.collateral_amount, ctx.accounts.mint.decimals)?;
     
     loan_request.status = LoanStatus::Liquidated;
     
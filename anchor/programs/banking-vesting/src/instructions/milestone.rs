@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::require_signer_approval;
+
+#[derive(Accounts)]
+pub struct AddVestingMilestone<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, company.key().as_ref(), vesting_schedule.beneficiary.as_ref(), &vesting_schedule.grant_id.to_le_bytes()],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<Milestone>(),
+        seeds = [MILESTONE_SEED, vesting_schedule.key().as_ref(), &[vesting_schedule.milestone_count]],
+        bump
+    )]
+    pub milestone: Account<'info, Milestone>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AchieveVestingMilestone<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, company.key().as_ref(), vesting_schedule.beneficiary.as_ref(), &vesting_schedule.grant_id.to_le_bytes()],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        constraint = milestone.schedule == vesting_schedule.key() @ BankingVestingError::Unauthorized,
+        constraint = !milestone.is_achieved @ BankingVestingError::MilestoneAlreadyAchieved
+    )]
+    pub milestone: Account<'info, Milestone>,
+    
+    /// Company authority or the milestone's designated attester
+    #[account(
+        constraint = attester.key() == company.authority
+            || attester.key() == milestone.attester @ BankingVestingError::Unauthorized
+    )]
+    pub attester: Signer<'info>,
+}
+
+pub fn add_vesting_milestone(
+    ctx: Context<AddVestingMilestone>,
+    weight_bps: u16,
+    attester: Pubkey,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    
+    require!(
+        vesting_schedule.vesting_type == VestingType::Milestone
+            || vesting_schedule.vesting_type == VestingType::Hybrid,
+        BankingVestingError::InvalidVestingType
+    );
+    
+    require!(
+        vesting_schedule.milestone_count < MAX_MILESTONES_PER_SCHEDULE,
+        BankingVestingError::InvalidVestingParameters
+    );
+    
+    let milestone_total_bps = vesting_schedule.milestone_total_bps
+        .checked_add(weight_bps)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // Milestones can only share out the portion of the grant reserved for them
+    require!(
+        weight_bps > 0
+            && milestone_total_bps <= vesting_schedule.release_terms.milestone_allocation_bps,
+        BankingVestingError::InvalidMilestoneWeight
+    );
+    
+    let milestone = &mut ctx.accounts.milestone;
+    let clock = Clock::get()?;
+    
+    milestone.schedule = vesting_schedule.key();
+    milestone.index = vesting_schedule.milestone_count;
+    milestone.weight_bps = weight_bps;
+    milestone.attester = attester;
+    milestone.is_achieved = false;
+    milestone.achieved_at = 0;
+    milestone.created_at = clock.unix_timestamp;
+    milestone.bump = ctx.bumps.milestone;
+    
+    vesting_schedule.milestone_count = vesting_schedule.milestone_count
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.milestone_total_bps = milestone_total_bps;
+    
+    emit!(MilestoneAdded {
+        milestone: milestone.key(),
+        schedule: vesting_schedule.key(),
+        index: milestone.index,
+        weight_bps,
+        attester,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn achieve_vesting_milestone(ctx: Context<AchieveVestingMilestone>) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let milestone = &mut ctx.accounts.milestone;
+    let clock = Clock::get()?;
+    
+    milestone.is_achieved = true;
+    milestone.achieved_at = clock.unix_timestamp;
+    
+    // Unlocks the tranche for the next claim
+    vesting_schedule.milestone_achieved_bps = vesting_schedule.milestone_achieved_bps
+        .checked_add(milestone.weight_bps)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(MilestoneAchieved {
+        milestone: milestone.key(),
+        schedule: vesting_schedule.key(),
+        index: milestone.index,
+        weight_bps: milestone.weight_bps,
+        achieved_bps: vesting_schedule.milestone_achieved_bps,
+        attested_by: ctx.accounts.attester.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
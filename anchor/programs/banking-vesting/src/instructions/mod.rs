@@ -1,6 +1,19 @@
 pub mod platform;
 pub mod company;
+pub mod governance;
+pub mod employee;
 pub mod vesting;
+pub mod milestone;
+pub mod performance;
+pub mod distributor;
+pub mod crank;
+pub mod acceleration;
+pub mod options;
+pub mod leave;
+pub mod quote;
+pub mod template;
+pub mod escrow_staking;
+pub mod withholding;
 pub mod banking;
 pub mod staking;
 pub mod lending;
@@ -9,7 +22,20 @@ pub mod emergency;
 
 pub use platform::*;
 pub use company::*;
+pub use governance::*;
+pub use employee::*;
 pub use vesting::*;
+pub use milestone::*;
+pub use performance::*;
+pub use distributor::*;
+pub use crank::*;
+pub use acceleration::*;
+pub use options::*;
+pub use leave::*;
+pub use quote::*;
+pub use template::*;
+pub use escrow_staking::*;
+pub use withholding::*;
 pub use banking::*;
 pub use staking::*;
 pub use lending::*;
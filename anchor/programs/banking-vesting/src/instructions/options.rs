@@ -0,0 +1,363 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+use super::vesting::transfer_from_vesting_vault;
+
+#[derive(Accounts)]
+pub struct SetOptionTerms<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    /// Terms are fixed once anything has been claimed or exercised
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked,
+        constraint = vesting_schedule.claimed_amount == 0 @ BankingVestingError::InvalidOptionTerms
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    /// Turning a grant into options changes what the beneficiary receives, so both parties sign
+    pub beneficiary: Signer<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExerciseVestedOptions<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = payment_mint.key() == vesting_schedule.option_terms.payment_mint @ BankingVestingError::InvalidMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = payment_mint,
+        token::authority = beneficiary,
+        token::token_program = payment_token_program
+    )]
+    pub beneficiary_payment_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Company treasury for strike proceeds
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = payment_mint,
+        associated_token::authority = company,
+        associated_token::token_program = payment_token_program
+    )]
+    pub company_payment_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    /// The payment mint may live under a different token program than the grant
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredOptions<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_revoked @ BankingVestingError::ExerciseWindowOpen,
+        constraint = vesting_schedule.option_terms.payment_mint != Pubkey::default() @ BankingVestingError::InvalidVestingType
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn set_option_terms(ctx: Context<SetOptionTerms>, option_terms: OptionTerms) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    
+    require!(
+        option_terms.strike_price > 0
+            && option_terms.payment_mint != Pubkey::default()
+            && option_terms.payment_mint != vesting_schedule.mint
+            && (0..=MAX_EXERCISE_WINDOW).contains(&option_terms.exercise_window),
+        BankingVestingError::InvalidOptionTerms
+    );
+    
+    let clock = Clock::get()?;
+    
+    vesting_schedule.option_terms = option_terms;
+    
+    emit!(OptionTermsSet {
+        schedule: vesting_schedule.key(),
+        strike_price: option_terms.strike_price,
+        payment_mint: option_terms.payment_mint,
+        exercise_window: option_terms.exercise_window,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn exercise_vested_options(ctx: Context<ExerciseVestedOptions>, quantity: u64) -> Result<()> {
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    let option_terms = vesting_schedule.option_terms;
+    
+    require!(
+        option_terms.payment_mint != Pubkey::default(),
+        BankingVestingError::InvalidVestingType
+    );
+    
+    require!(
+        quantity > 0,
+        BankingVestingError::InvalidAmount
+    );
+    
+    // Terminated holders keep their vested options only for the exercise window
+    if vesting_schedule.is_revoked {
+        let window_end = vesting_schedule.revoked_at
+            .checked_add(option_terms.exercise_window)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        require!(
+            clock.unix_timestamp <= window_end,
+            BankingVestingError::ExerciseWindowClosed
+        );
+    }
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let exercisable_amount = calculate_claimable_amount(vesting_schedule, clock.unix_timestamp)?;
+    
+    require!(
+        quantity <= exercisable_amount,
+        BankingVestingError::NoTokensAvailable
+    );
+    
+    let exercise_cost = calculate_exercise_cost(
+        option_terms.strike_price,
+        quantity,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    // Any transfer fee on the payment mint is on the beneficiary, the company nets the full strike
+    let payment_amount = calculate_gross_transfer_amount(&ctx.accounts.payment_mint, exercise_cost)?;
+    
+    require!(
+        ctx.accounts.beneficiary_payment_account.amount >= payment_amount,
+        BankingVestingError::InsufficientBalance
+    );
+    
+    let payment_ctx = CpiContext::new(
+        ctx.accounts.payment_token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.beneficiary_payment_account.to_account_info(),
+            mint: ctx.accounts.payment_mint.to_account_info(),
+            to: ctx.accounts.company_payment_account.to_account_info(),
+            authority: ctx.accounts.beneficiary.to_account_info(),
+        },
+    );
+    
+    transfer_checked(payment_ctx, payment_amount, ctx.accounts.payment_mint.decimals)?;
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.beneficiary_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        quantity,
+    )?;
+    
+    vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+        .checked_add(quantity)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.last_claimed = clock.unix_timestamp;
+    
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(quantity)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(OptionsExercised {
+        schedule: vesting_schedule.key(),
+        beneficiary: ctx.accounts.beneficiary.key(),
+        quantity,
+        exercise_cost,
+        payment_mint: option_terms.payment_mint,
+        total_exercised: vesting_schedule.claimed_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Returns vested options nobody exercised once the post-termination window lapses
+pub fn reclaim_expired_options(ctx: Context<ReclaimExpiredOptions>) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    let window_end = vesting_schedule.revoked_at
+        .checked_add(vesting_schedule.option_terms.exercise_window)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        clock.unix_timestamp > window_end,
+        BankingVestingError::ExerciseWindowOpen
+    );
+    
+    let expired_amount = vesting_schedule.total_amount
+        .checked_sub(vesting_schedule.claimed_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    require!(
+        expired_amount > 0,
+        BankingVestingError::NoTokensAvailable
+    );
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.company_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        expired_amount,
+    )?;
+    
+    vesting_schedule.total_amount = vesting_schedule.claimed_amount;
+    
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(expired_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(ExpiredOptionsReclaimed {
+        schedule: vesting_schedule.key(),
+        company: company.key(),
+        amount: expired_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+
+#[derive(Accounts)]
+#[instruction(metric_id: u32)]
+pub struct CreatePerformanceMetric<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PerformanceMetric>(),
+        seeds = [METRIC_SEED, company.key().as_ref(), &metric_id.to_le_bytes()],
+        bump
+    )]
+    pub performance_metric: Account<'info, PerformanceMetric>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportPerformanceMetric<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [METRIC_SEED, performance_metric.company.as_ref(), &performance_metric.metric_id.to_le_bytes()],
+        bump = performance_metric.bump,
+        constraint = performance_metric.reporter == reporter.key() @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Account<'info, PerformanceMetric>,
+    
+    pub reporter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPerformanceTarget<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, company.key().as_ref(), vesting_schedule.beneficiary.as_ref(), &vesting_schedule.grant_id.to_le_bytes()],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked,
+        constraint = vesting_schedule.performance_metric == Pubkey::default() @ BankingVestingError::PerformanceTargetAlreadySet
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        seeds = [METRIC_SEED, company.key().as_ref(), &performance_metric.metric_id.to_le_bytes()],
+        bump = performance_metric.bump
+    )]
+    pub performance_metric: Account<'info, PerformanceMetric>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn create_performance_metric(
+    ctx: Context<CreatePerformanceMetric>,
+    metric_id: u32,
+    reporter: Pubkey,
+) -> Result<()> {
+    let performance_metric = &mut ctx.accounts.performance_metric;
+    let clock = Clock::get()?;
+    
+    performance_metric.company = ctx.accounts.company.key();
+    performance_metric.metric_id = metric_id;
+    performance_metric.reporter = reporter;
+    performance_metric.current_value = 0;
+    performance_metric.peak_value = 0;
+    performance_metric.last_reported = 0;
+    performance_metric.created_at = clock.unix_timestamp;
+    performance_metric.bump = ctx.bumps.performance_metric;
+    
+    emit!(PerformanceMetricCreated {
+        metric: performance_metric.key(),
+        company: performance_metric.company,
+        metric_id,
+        reporter,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn report_performance_metric(ctx: Context<ReportPerformanceMetric>, value: u64) -> Result<()> {
+    let performance_metric = &mut ctx.accounts.performance_metric;
+    let clock = Clock::get()?;
+    
+    performance_metric.current_value = value;
+    performance_metric.peak_value = performance_metric.peak_value.max(value);
+    performance_metric.last_reported = clock.unix_timestamp;
+    
+    emit!(PerformanceMetricReported {
+        metric: performance_metric.key(),
+        reporter: ctx.accounts.reporter.key(),
+        value,
+        peak_value: performance_metric.peak_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn set_performance_target(
+    ctx: Context<SetPerformanceTarget>,
+    bands: Vec<PerformanceBand>,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    validate_performance_bands(&bands)?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    require!(
+        vesting_schedule.vesting_type == VestingType::Performance,
+        BankingVestingError::InvalidVestingType
+    );
+    
+    vesting_schedule.performance_metric = ctx.accounts.performance_metric.key();
+    vesting_schedule.performance_bands[..bands.len()].copy_from_slice(&bands);
+    vesting_schedule.performance_band_count = bands.len() as u8;
+    
+    // Bands already reached before the target was attached count immediately
+    refresh_performance_unlock(vesting_schedule, ctx.accounts.performance_metric.peak_value);
+    
+    emit!(PerformanceTargetSet {
+        schedule: vesting_schedule.key(),
+        metric: vesting_schedule.performance_metric,
+        band_count: vesting_schedule.performance_band_count,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
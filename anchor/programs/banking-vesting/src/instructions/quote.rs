@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+
+/// Snapshot of a schedule, returned to callers through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingQuote {
+    pub vested_amount: u64,
+    pub claimed_amount: u64,
+    pub claimable_amount: u64,
+    pub next_unlock_time: i64,
+    pub fully_vested_at: i64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteVestingSchedule<'info> {
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+}
+
+/// Read-only, nothing is written back. Quotes at `timestamp` or the current clock.
+pub fn quote_vesting_schedule(
+    ctx: Context<QuoteVestingSchedule>,
+    timestamp: Option<i64>,
+) -> Result<VestingQuote> {
+    let quote_time = match timestamp {
+        Some(timestamp) => timestamp,
+        None => Clock::get()?.unix_timestamp,
+    };
+    
+    let mut vesting_schedule = (*ctx.accounts.vesting_schedule).clone();
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(&mut vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let vested_amount = calculate_schedule_vested_amount(&vesting_schedule, quote_time)?;
+    
+    // A quote in the past can sit below what has already been claimed
+    let claimable_amount = vested_amount.saturating_sub(vesting_schedule.claimed_amount);
+    
+    let (next_unlock_time, fully_vested_at) = calculate_vesting_timeline(&vesting_schedule, quote_time)?;
+    
+    Ok(VestingQuote {
+        vested_amount,
+        claimed_amount: vesting_schedule.claimed_amount,
+        claimable_amount,
+        next_unlock_time,
+        fully_vested_at,
+    })
+}
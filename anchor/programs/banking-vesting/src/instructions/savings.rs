@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::constants::*;
@@ -33,27 +33,29 @@ pub struct CreateSavingsAccount<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = owner
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = owner,
         associated_token::mint = mint,
-        associated_token::authority = savings_account
+        associated_token::authority = savings_account,
+        associated_token::token_program = token_program
     )]
-    pub savings_token_account: Account<'info, TokenAccount>,
+    pub savings_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub owner: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -75,19 +77,20 @@ pub struct CompoundInterest<'info> {
     )]
     pub savings_account: Account<'info, SavingsAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = savings_account
+        associated_token::authority = savings_account,
+        associated_token::token_program = token_program
     )]
-    pub savings_token_account: Account<'info, TokenAccount>,
+    pub savings_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn create_savings_account(ctx: Context<CreateSavingsAccount>, apy_rate: u16) -> Result<()> {
@@ -99,7 +102,8 @@ pub fn create_savings_account(ctx: Context<CreateSavingsAccount>, apy_rate: u16)
     
     // Require minimum deposit
     require!(
-        ctx.accounts.owner_token_account.amount >= MIN_SAVINGS_DEPOSIT,
+        ctx.accounts.owner_token_account.amount
+            >= scale_to_decimals(MIN_SAVINGS_DEPOSIT, ctx.accounts.mint.decimals)?,
         BankingVestingError::InvalidAmount
     );
     
@@ -175,7 +179,10 @@ pub fn compound_interest(ctx: Context<CompoundInterest>) -> Result<()> {
 }
 
 pub fn deposit_to_savings(ctx: Context<DepositToSavings>, amount: u64) -> Result<()> {
-    require!(amount >= MIN_SAVINGS_DEPOSIT, BankingVestingError::InvalidAmount);
+    require!(
+        amount >= scale_to_decimals(MIN_SAVINGS_DEPOSIT, ctx.accounts.mint.decimals)?,
+        BankingVestingError::InvalidAmount
+    );
     
     require!(
         ctx.accounts.owner_token_account.amount >= amount,
@@ -192,17 +199,21 @@ pub fn deposit_to_savings(ctx: Context<DepositToSavings>, amount: u64) -> Result
     // Transfer tokens to savings account
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.owner_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.savings_token_account.to_account_info(),
             authority: ctx.accounts.owner.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, amount)?;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    // Credit what arrived, not what was sent, on transfer-fee mints
+    let received_amount = calculate_received_amount(&ctx.accounts.mint, amount)?;
     
     savings_account.balance = savings_account.balance
-        .checked_add(amount)
+        .checked_add(received_amount)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
     Ok(())
@@ -237,15 +248,16 @@ pub fn withdraw_from_savings(ctx: Context<WithdrawFromSavings>, amount: u64) ->
     
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.savings_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.owner_token_account.to_account_info(),
             authority: savings_account.to_account_info(),
         },
         signer_seeds,
     );
     
-    transfer(transfer_ctx, amount)?;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
     
     savings_account.balance = savings_account.balance
         .checked_sub(amount)
@@ -271,26 +283,28 @@ pub struct DepositToSavings<'info> {
     )]
     pub savings_account: Account<'info, SavingsAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = owner
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = savings_account
+        associated_token::authority = savings_account,
+        associated_token::token_program = token_program
     )]
-    pub savings_token_account: Account<'info, TokenAccount>,
+    pub savings_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub owner: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -310,24 +324,26 @@ pub struct WithdrawFromSavings<'info> {
     )]
     pub savings_account: Account<'info, SavingsAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = owner
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
     )]
-    pub owner_token_account: Account<'info, TokenAccount>,
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = savings_account
+        associated_token::authority = savings_account,
+        associated_token::token_program = token_program
     )]
-    pub savings_token_account: Account<'info, TokenAccount>,
+    pub savings_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub owner: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::constants::*;
@@ -33,27 +33,29 @@ pub struct StakeTokens<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
-        associated_token::authority = staking_pool
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -83,30 +85,35 @@ pub struct UnstakeTokens<'info> {
     )]
     pub banking_account: Account<'info, BankingAccount>,
     
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = staking_pool
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
-    require!(amount >= MIN_STAKE_AMOUNT, BankingVestingError::InvalidAmount);
+    require!(
+        amount >= scale_to_decimals(MIN_STAKE_AMOUNT, ctx.accounts.mint.decimals)?,
+        BankingVestingError::InvalidAmount
+    );
     
     require!(
         ctx.accounts.user_token_account.amount >= amount,
@@ -134,30 +141,34 @@ pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
     // Transfer tokens to staking pool
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.pool_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         },
     );
     
-    transfer(transfer_ctx, amount)?;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    // Credit what arrived, not what was sent, on transfer-fee mints
+    let received_amount = calculate_received_amount(&ctx.accounts.mint, amount)?;
     
     // Update accounts
     banking_account.staked_amount = banking_account.staked_amount
-        .checked_add(amount)
+        .checked_add(received_amount)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
     banking_account.last_interaction = clock.unix_timestamp;
     
     staking_pool.total_staked = staking_pool.total_staked
-        .checked_add(amount)
+        .checked_add(received_amount)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
     emit!(TokensStaked {
         pool: staking_pool.key(),
         user: ctx.accounts.user.key(),
-        amount,
+        amount: received_amount,
         total_staked: staking_pool.total_staked,
         timestamp: clock.unix_timestamp,
     });
@@ -184,11 +195,18 @@ pub fn unstake_tokens(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
         clock.unix_timestamp - banking_account.last_interaction,
     )?;
     
-    let total_withdrawal = amount + rewards;
+    let total_withdrawal = amount
+        .checked_add(rewards)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // Rewards must come out of the pool's surplus, never other stakers' principal or
+    // escrowed vesting tokens
+    let required_balance = staking_pool.total_staked
+        .checked_add(rewards)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
-    // Check if pool has enough tokens
     require!(
-        ctx.accounts.pool_token_account.amount >= total_withdrawal,
+        ctx.accounts.pool_token_account.amount >= required_balance,
         BankingVestingError::InsufficientBalance
     );
     
@@ -203,15 +221,16 @@ pub fn unstake_tokens(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
     
     let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.pool_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: staking_pool.to_account_info(),
         },
         signer_seeds,
     );
     
-    transfer(transfer_ctx, total_withdrawal)?;
+    transfer_checked(transfer_ctx, total_withdrawal, ctx.accounts.mint.decimals)?;
     
     // Update accounts
     banking_account.staked_amount = banking_account.staked_amount
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+use super::vesting::*;
+
+#[derive(Accounts)]
+#[instruction(template_id: u32)]
+pub struct CreateVestingTemplate<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<VestingTemplate>(),
+        seeds = [TEMPLATE_SEED, company.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, VestingTemplate>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVestingTemplate<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [TEMPLATE_SEED, company.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, VestingTemplate>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Same accounts as a direct grant, terms come from the template
+#[derive(Accounts)]
+pub struct CreateVestingScheduleFromTemplate<'info> {
+    pub grant: CreateVestingSchedule<'info>,
+    
+    #[account(
+        mut,
+        seeds = [TEMPLATE_SEED, grant.company.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, VestingTemplate>,
+}
+
+pub fn create_vesting_template(
+    ctx: Context<CreateVestingTemplate>,
+    template_id: u32,
+    terms: TemplateTerms,
+) -> Result<()> {
+    validate_template_terms(&terms)?;
+    
+    let template = &mut ctx.accounts.template;
+    let clock = Clock::get()?;
+    
+    template.company = ctx.accounts.company.key();
+    template.template_id = template_id;
+    template.version = 1;
+    template.terms = terms;
+    template.schedules_created = 0;
+    template.created_at = clock.unix_timestamp;
+    template.updated_at = clock.unix_timestamp;
+    template.bump = ctx.bumps.template;
+    
+    emit!(VestingTemplateUpdated {
+        template: template.key(),
+        company: template.company,
+        template_id,
+        version: template.version,
+        vesting_type: vesting_type_str(terms.vesting_type).to_string(),
+        cliff_duration: terms.cliff_duration,
+        vesting_duration: terms.vesting_duration,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Edits only affect schedules created afterwards, existing ones hold a copy of the terms
+pub fn update_vesting_template(ctx: Context<UpdateVestingTemplate>, terms: TemplateTerms) -> Result<()> {
+    validate_template_terms(&terms)?;
+    
+    let template = &mut ctx.accounts.template;
+    let clock = Clock::get()?;
+    
+    template.version = template.version
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    template.terms = terms;
+    template.updated_at = clock.unix_timestamp;
+    
+    emit!(VestingTemplateUpdated {
+        template: template.key(),
+        company: template.company,
+        template_id: template.template_id,
+        version: template.version,
+        vesting_type: vesting_type_str(terms.vesting_type).to_string(),
+        cliff_duration: terms.cliff_duration,
+        vesting_duration: terms.vesting_duration,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn create_vesting_schedule_from_template(
+    ctx: Context<CreateVestingScheduleFromTemplate>,
+    beneficiary: Pubkey,
+    total_amount: u64,
+    start_time: i64,
+    template_version: u32,
+) -> Result<()> {
+    let template = &mut ctx.accounts.template;
+    
+    // Guards against granting on terms HR edited after the caller looked them up
+    require!(
+        template.version == template_version,
+        BankingVestingError::TemplateVersionMismatch
+    );
+    
+    let terms = template.terms;
+    
+    require_grant_approval(
+        &ctx.accounts.grant.company,
+        &ctx.accounts.grant.authority.key(),
+        total_amount,
+        ctx.remaining_accounts,
+    )?;
+    
+    initialize_vesting_schedule(
+        &mut ctx.accounts.grant,
+        &ctx.bumps.grant,
+        beneficiary,
+        total_amount,
+        start_time,
+        terms.cliff_duration,
+        terms.vesting_duration,
+        terms.vesting_type,
+        terms.release_terms,
+    )?;
+    
+    let vesting_schedule = &mut ctx.accounts.grant.vesting_schedule;
+    vesting_schedule.acceleration_policy = terms.acceleration_policy;
+    vesting_schedule.template = template.key();
+    vesting_schedule.template_version = template.version;
+    
+    template.schedules_created = template.schedules_created
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, Transfer, transfer};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked, close_account, transfer_checked,
+};
+use anchor_spl::associated_token::{AssociatedToken, get_associated_token_address_with_program_id};
 use crate::state::*;
 use crate::constants::*;
 use crate::events::*;
 use crate::errors::BankingVestingError;
 use crate::utils::*;
+use super::escrow_staking::transfer_from_staking_pool;
 
 #[derive(Accounts)]
 #[instruction(beneficiary: Pubkey)]
@@ -20,37 +23,66 @@ pub struct CreateVestingSchedule<'info> {
     
     #[account(
         mut,
-        seeds = [COMPANY_SEED, company.authority.as_ref(), &company.name],
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
         bump = company.bump,
         constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
     )]
     pub company: Account<'info, Company>,
     
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<BeneficiaryGrants>(),
+        seeds = [GRANTS_SEED, company.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub beneficiary_grants: Account<'info, BeneficiaryGrants>,
+    
     #[account(
         init,
         payer = authority,
         space = 8 + std::mem::size_of::<VestingSchedule>(),
-        seeds = [VESTING_SEED, company.key().as_ref(), beneficiary.key().as_ref()],
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &beneficiary_grants.grant_count.to_le_bytes()
+        ],
         bump
     )]
     pub vesting_schedule: Account<'info, VestingSchedule>,
     
-    pub mint: Account<'info, Mint>,
+    #[account(
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = company
+        associated_token::authority = company,
+        associated_token::token_program = token_program
     )]
-    pub company_token_account: Account<'info, TokenAccount>,
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Escrow holding the full grant until it is claimed
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = mint,
-        associated_token::authority = beneficiary
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
     )]
-    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Beneficiary address
     pub beneficiary: UncheckedAccount<'info>,
@@ -58,7 +90,7 @@ pub struct CreateVestingSchedule<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -74,81 +106,611 @@ pub struct ClaimVestedTokens<'info> {
     pub platform: Account<'info, Platform>,
     
     #[account(
-        seeds = [COMPANY_SEED, company.authority.as_ref(), &company.name],
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
+        constraint = vesting_schedule.option_terms.payment_mint == Pubkey::default() @ BankingVestingError::OptionExerciseRequired
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Receives the withheld share, only needed when withholding applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub withholding_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Schedules to settle are passed as remaining accounts: for each one the schedule
+/// and its escrow vault (both writable), followed by its performance metric when
+/// the schedule references one
+#[derive(Accounts)]
+pub struct ClaimAllVestedTokens<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
         bump = company.bump
     )]
     pub company: Account<'info, Company>,
     
+    #[account(
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Receives the withheld share, only needed when withholding applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub withholding_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVestingSchedule<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    /// Required when part of the escrow is staked, the principal is pulled back first
+    #[account(
+        mut,
+        seeds = [STAKING_SEED, mint.key().as_ref()],
+        bump = staking_pool.bump
+    )]
+    pub staking_pool: Option<Account<'info, StakingPool>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = staking_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Beneficiary address, validated against the schedule
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AmendVestingSchedule<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
     #[account(
         mut,
-        seeds = [VESTING_SEED, company.key().as_ref(), beneficiary.key().as_ref()],
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
         bump = vesting_schedule.bump,
         constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
-        constraint = !vesting_schedule.is_revoked @ BankingVestingError::Unauthorized
+        constraint = !vesting_schedule.is_revoked @ BankingVestingError::VestingRevoked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = performance_metric.key() == vesting_schedule.performance_metric @ BankingVestingError::Unauthorized
+    )]
+    pub performance_metric: Option<Account<'info, PerformanceMetric>>,
+    
+    /// Both parties have to agree to new terms
+    pub beneficiary: Signer<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVestingSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    /// Fully claimed, or revoked and paid out down to the retained amount, with nothing
+    /// left in a staking pool or owed in stake rewards
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.claimed_amount == vesting_schedule.total_amount @ BankingVestingError::ScheduleNotSettled,
+        constraint = vesting_schedule.staked_amount == 0
+            && vesting_schedule.stake_rewards_accrued == 0 @ BankingVestingError::ScheduleNotSettled
     )]
     pub vesting_schedule: Account<'info, VestingSchedule>,
     
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [GRANTS_SEED, company.key().as_ref(), vesting_schedule.beneficiary.as_ref()],
+        bump = beneficiary_grants.bump
+    )]
+    pub beneficiary_grants: Account<'info, BeneficiaryGrants>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = company
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Receives any tokens sent to the vault from outside the schedule
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = company,
+        associated_token::token_program = token_program
+    )]
+    pub company_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct TransferVestingSchedule<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ BankingVestingError::PlatformPaused
+    )]
+    pub platform: Account<'info, Platform>,
+    
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            beneficiary.key().as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.beneficiary == beneficiary.key() @ BankingVestingError::Unauthorized,
+        constraint = vesting_schedule.staked_amount == 0 @ BankingVestingError::EscrowStillStaked
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        seeds = [GRANTS_SEED, company.key().as_ref(), beneficiary.key().as_ref()],
+        bump = beneficiary_grants.bump
+    )]
+    pub beneficiary_grants: Account<'info, BeneficiaryGrants>,
+    
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = 8 + std::mem::size_of::<BeneficiaryGrants>(),
+        seeds = [GRANTS_SEED, company.key().as_ref(), new_beneficiary.key().as_ref()],
+        bump
+    )]
+    pub new_beneficiary_grants: Account<'info, BeneficiaryGrants>,
+    
+    /// Schedule re-derived under the new owner so PDA seeds keep matching the beneficiary
+    #[account(
+        init,
+        payer = beneficiary,
+        space = 8 + std::mem::size_of::<VestingSchedule>(),
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            new_beneficiary.key().as_ref(),
+            &new_beneficiary_grants.grant_count.to_le_bytes()
+        ],
+        bump
     )]
-    pub company_token_account: Account<'info, TokenAccount>,
+    pub new_vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         associated_token::mint = mint,
-        associated_token::authority = beneficiary
+        associated_token::authority = vesting_schedule,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = new_vesting_schedule,
+        associated_token::token_program = token_program
     )]
-    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    pub new_vesting_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub beneficiary: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// CHECK: New owner of the schedule, may be a custody provider
+    #[account(
+        constraint = new_beneficiary.key() != beneficiary.key() @ BankingVestingError::InvalidTransferRecipient
+    )]
+    pub new_beneficiary: UncheckedAccount<'info>,
+    
+    /// Required only when the company has opted into transfer approval
+    #[account(
+        constraint = company_authority.key() == company.authority @ BankingVestingError::Unauthorized
+    )]
+    pub company_authority: Option<Signer<'info>>,
+    
+    /// Required only when the schedule is linked to an employee record
+    #[account(
+        mut,
+        constraint = employee.key() == vesting_schedule.employee @ BankingVestingError::Unauthorized
+    )]
+    pub employee: Option<Account<'info, Employee>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_vesting_schedule(
+    ctx: Context<CreateVestingSchedule>,
+    beneficiary: Pubkey,
+    total_amount: u64,
+    start_time: i64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+    vesting_type: VestingType,
+    release_terms: ReleaseTerms,
+) -> Result<()> {
+    require_grant_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        total_amount,
+        ctx.remaining_accounts,
+    )?;
+    
+    initialize_vesting_schedule(
+        ctx.accounts,
+        &ctx.bumps,
+        beneficiary,
+        total_amount,
+        start_time,
+        cliff_duration,
+        vesting_duration,
+        vesting_type,
+        release_terms,
+    )
 }
 
-pub fn create_vesting_schedule(
-    ctx: Context<CreateVestingSchedule>,
+/// Validates, funds and initializes a new grant. Shared by direct creation and
+/// creation from a company template.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_vesting_schedule(
+    accounts: &mut CreateVestingSchedule,
+    bumps: &CreateVestingScheduleBumps,
     beneficiary: Pubkey,
     total_amount: u64,
     start_time: i64,
     cliff_duration: i64,
     vesting_duration: i64,
     vesting_type: VestingType,
+    release_terms: ReleaseTerms,
 ) -> Result<()> {
+    // The schedule, its escrow and the grant counter are all derived from the account,
+    // a different stored beneficiary could never re-derive them
+    require_keys_eq!(
+        accounts.beneficiary.key(),
+        beneficiary,
+        BankingVestingError::Unauthorized
+    );
+    
     validate_vesting_parameters(start_time, cliff_duration, vesting_duration)?;
+    validate_release_terms(vesting_type, &release_terms, vesting_duration)?;
     
     require!(
         total_amount > 0,
         BankingVestingError::InvalidAmount
     );
     
+    // Transfer-fee mints are grossed up so the escrow ends up holding the full grant
+    let funding_amount = calculate_gross_transfer_amount(&accounts.mint, total_amount)?;
+    
     require!(
-        ctx.accounts.company_token_account.amount >= total_amount,
+        accounts.company_token_account.amount >= funding_amount,
         BankingVestingError::InsufficientBalance
     );
     
-    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
-    let company = &mut ctx.accounts.company;
-    let platform = &mut ctx.accounts.platform;
+    let beneficiary_grants = &mut accounts.beneficiary_grants;
+    
+    require!(
+        (beneficiary_grants.active_grants as usize) < MAX_VESTING_SCHEDULES_PER_USER,
+        BankingVestingError::TooManyVestingSchedules
+    );
+    
+    if beneficiary_grants.company == Pubkey::default() {
+        beneficiary_grants.company = accounts.company.key();
+        beneficiary_grants.beneficiary = beneficiary;
+        beneficiary_grants.grant_count = 0;
+        beneficiary_grants.active_grants = 0;
+        beneficiary_grants.bump = bumps.beneficiary_grants;
+    }
+    
+    let grant_id = beneficiary_grants.grant_count;
+    
+    beneficiary_grants.grant_count = beneficiary_grants.grant_count
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    beneficiary_grants.active_grants = beneficiary_grants.active_grants
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    let vesting_schedule = &mut accounts.vesting_schedule;
+    let company = &mut accounts.company;
+    let platform = &mut accounts.platform;
     let clock = Clock::get()?;
     
     vesting_schedule.company = company.key();
     vesting_schedule.beneficiary = beneficiary;
-    vesting_schedule.mint = ctx.accounts.mint.key();
+    vesting_schedule.grant_id = grant_id;
+    vesting_schedule.mint = accounts.mint.key();
     vesting_schedule.total_amount = total_amount;
     vesting_schedule.claimed_amount = 0;
     vesting_schedule.start_time = start_time;
     vesting_schedule.cliff_duration = cliff_duration;
     vesting_schedule.vesting_duration = vesting_duration;
     vesting_schedule.vesting_type = vesting_type;
+    vesting_schedule.release_terms = release_terms;
+    if vesting_type == VestingType::Milestone {
+        // Pure milestone grants reserve the whole amount for milestone tranches
+        vesting_schedule.release_terms.milestone_allocation_bps = BASIS_POINTS as u16;
+    }
     vesting_schedule.is_revoked = false;
+    vesting_schedule.revoked_at = 0;
+    vesting_schedule.milestone_count = 0;
+    vesting_schedule.milestone_total_bps = 0;
+    vesting_schedule.milestone_achieved_bps = 0;
+    vesting_schedule.performance_metric = Pubkey::default();
+    vesting_schedule.performance_bands = [PerformanceBand::default(); MAX_PERFORMANCE_BANDS];
+    vesting_schedule.performance_band_count = 0;
+    vesting_schedule.performance_unlocked_bps = 0;
+    vesting_schedule.claim_delegate = Pubkey::default();
+    vesting_schedule.permissionless_claims = false;
+    vesting_schedule.crank_tip_bps = 0;
+    vesting_schedule.acceleration_policy = AccelerationPolicy::default();
+    vesting_schedule.accelerated_amount = 0;
+    vesting_schedule.accelerated_at = 0;
+    vesting_schedule.vested_at_acceleration = 0;
+    vesting_schedule.option_terms = OptionTerms::default();
+    vesting_schedule.amendment_count = 0;
+    vesting_schedule.last_amended = 0;
+    vesting_schedule.paused_at = 0;
+    vesting_schedule.total_paused_duration = 0;
+    vesting_schedule.template = Pubkey::default();
+    vesting_schedule.template_version = 0;
+    vesting_schedule.staked_amount = 0;
+    vesting_schedule.staked_at = 0;
+    vesting_schedule.stake_rewards_accrued = 0;
+    vesting_schedule.stake_reward_carry = 0;
+    // Later changes to the company's terms only reach new grants
+    vesting_schedule.withholding = company.withholding;
+    vesting_schedule.employee = Pubkey::default();
     vesting_schedule.created_at = clock.unix_timestamp;
     vesting_schedule.last_claimed = 0;
-    vesting_schedule.bump = ctx.bumps.vesting_schedule;
+    vesting_schedule.bump = bumps.vesting_schedule;
+    
+    // Lock the full grant in the schedule's escrow so it cannot be spent elsewhere
+    let company_creator = company.creator;
+    let company_name = company.name;
+    let seeds = &[
+        COMPANY_SEED,
+        company_creator.as_ref(),
+        &company_name,
+        &[company.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: accounts.company_token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            to: accounts.vesting_vault.to_account_info(),
+            authority: company.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, funding_amount, accounts.mint.decimals)?;
     
     company.allocated_supply = company.allocated_supply
         .checked_add(total_amount)
@@ -162,20 +724,14 @@ pub fn create_vesting_schedule(
         .checked_add(1)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
-    let vesting_type_str = match vesting_type {
-        VestingType::Linear => "Linear",
-        VestingType::Cliff => "Cliff",
-        VestingType::Milestone => "Milestone",
-        VestingType::Performance => "Performance",
-        VestingType::Hybrid => "Hybrid",
-    };
-    
     emit!(VestingScheduleCreated {
         schedule: vesting_schedule.key(),
         company: company.key(),
         beneficiary,
+        grant_id,
         total_amount,
-        vesting_type: vesting_type_str.to_string(),
+        vesting_type: vesting_type_str(vesting_type).to_string(),
+        release_interval: release_terms.release_interval,
         timestamp: clock.unix_timestamp,
     });
     
@@ -191,58 +747,841 @@ pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
         BankingVestingError::VestingNotStarted
     );
     
-    let vested_amount = calculate_vested_amount(
-        vesting_schedule.total_amount,
-        vesting_schedule.start_time,
-        vesting_schedule.cliff_duration,
-        vesting_schedule.vesting_duration,
-        clock.unix_timestamp,
+    require_performance_metric(vesting_schedule, ctx.accounts.performance_metric.as_ref())?;
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let claimable_amount = calculate_claimable_amount(vesting_schedule, clock.unix_timestamp)?;
+    
+    require!(
+        claimable_amount > 0,
+        BankingVestingError::NoTokensAvailable
+    );
+    
+    let withholding = vesting_schedule.withholding;
+    let withheld_amount = transfer_withholding(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.withholding_token_account.as_ref(),
+        ctx.accounts.token_program.to_account_info(),
+        &withholding,
+        claimable_amount,
     )?;
     
-    let claimable_amount = vested_amount
-        .checked_sub(vesting_schedule.claimed_amount)
+    let payout = claimable_amount
+        .checked_sub(withheld_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        ctx.accounts.beneficiary_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        payout,
+    )?;
+    
+    vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+        .checked_add(claimable_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.last_claimed = clock.unix_timestamp;
+    
+    // Claimed tokens are no longer locked on the company's behalf
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(claimable_amount)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
+    emit!(TokensClaimed {
+        schedule: vesting_schedule.key(),
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount: payout,
+        withheld_amount,
+        withholding_account: withholding.destination,
+        total_claimed: vesting_schedule.claimed_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn claim_all_vested_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimAllVestedTokens<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let company_key = ctx.accounts.company.key();
+    let mint_key = ctx.accounts.mint.key();
+    let beneficiary_key = ctx.accounts.beneficiary.key();
+    let mut remaining_accounts = ctx.remaining_accounts.iter();
+    let mut total_claimed: u64 = 0;
+    
+    while let Some(schedule_info) = remaining_accounts.next() {
+        let mut vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+        
+        require!(
+            vesting_schedule.company == company_key
+                && vesting_schedule.beneficiary == beneficiary_key,
+            BankingVestingError::Unauthorized
+        );
+        require!(
+            vesting_schedule.mint == mint_key,
+            BankingVestingError::InvalidMint
+        );
+        require!(
+            vesting_schedule.option_terms.payment_mint == Pubkey::default(),
+            BankingVestingError::OptionExerciseRequired
+        );
+        
+        let grant_id_bytes = vesting_schedule.grant_id.to_le_bytes();
+        let expected_schedule = Pubkey::create_program_address(
+            &[
+                VESTING_SEED,
+                company_key.as_ref(),
+                beneficiary_key.as_ref(),
+                &grant_id_bytes,
+                &[vesting_schedule.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| BankingVestingError::Unauthorized)?;
+        require_keys_eq!(schedule_info.key(), expected_schedule, BankingVestingError::Unauthorized);
+        
+        let vault_info = remaining_accounts
+            .next()
+            .ok_or(BankingVestingError::AccountNotFound)?;
+        require_keys_eq!(
+            vault_info.key(),
+            get_associated_token_address_with_program_id(
+                &schedule_info.key(),
+                &mint_key,
+                &ctx.accounts.token_program.key()
+            ),
+            BankingVestingError::Unauthorized
+        );
+        
+        if vesting_schedule.performance_metric != Pubkey::default() {
+            let metric_info = remaining_accounts
+                .next()
+                .ok_or(BankingVestingError::AccountNotFound)?;
+            require_keys_eq!(
+                metric_info.key(),
+                vesting_schedule.performance_metric,
+                BankingVestingError::Unauthorized
+            );
+            let performance_metric = Account::<PerformanceMetric>::try_from(metric_info)?;
+            refresh_performance_unlock(&mut vesting_schedule, performance_metric.peak_value);
+        }
+        
+        let claimable_amount = calculate_claimable_amount(&vesting_schedule, clock.unix_timestamp)?;
+        
+        // Schedules with nothing vested yet are skipped rather than costing a CPI
+        if claimable_amount == 0 {
+            continue;
+        }
+        
+        let withholding = vesting_schedule.withholding;
+        let withheld_amount = transfer_withholding(
+            &vesting_schedule,
+            &ctx.accounts.mint,
+            vault_info.clone(),
+            ctx.accounts.withholding_token_account.as_ref(),
+            ctx.accounts.token_program.to_account_info(),
+            &withholding,
+            claimable_amount,
+        )?;
+        
+        let payout = claimable_amount
+            .checked_sub(withheld_amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        transfer_from_vesting_vault(
+            &vesting_schedule,
+            &ctx.accounts.mint,
+            vault_info.clone(),
+            ctx.accounts.beneficiary_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            payout,
+        )?;
+        
+        vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+            .checked_add(claimable_amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        vesting_schedule.last_claimed = clock.unix_timestamp;
+        vesting_schedule.exit(ctx.program_id)?;
+        
+        total_claimed = total_claimed
+            .checked_add(claimable_amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        emit!(TokensClaimed {
+            schedule: schedule_info.key(),
+            beneficiary: beneficiary_key,
+            amount: payout,
+            withheld_amount,
+            withholding_account: withholding.destination,
+            total_claimed: vesting_schedule.claimed_amount,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
     require!(
-        claimable_amount > 0,
+        total_claimed > 0,
         BankingVestingError::NoTokensAvailable
     );
     
-    let company_authority = ctx.accounts.company.authority;
-    let company_name = &ctx.accounts.company.name;
+    let company = &mut ctx.accounts.company;
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(total_claimed)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    Ok(())
+}
+
+pub fn revoke_vesting_schedule(
+    ctx: Context<RevokeVestingSchedule>,
+    revoke_at: i64,
+    leaver_policy: LeaverPolicy,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    require_performance_metric(
+        &ctx.accounts.vesting_schedule,
+        ctx.accounts.performance_metric.as_ref(),
+    )?;
+    
+    let accounts = ctx.accounts;
+    
+    settle_revocation(
+        &mut accounts.vesting_schedule,
+        &mut accounts.company,
+        &accounts.mint,
+        &accounts.vesting_vault,
+        &accounts.company_token_account,
+        &accounts.beneficiary_token_account,
+        accounts.performance_metric.as_ref(),
+        accounts.staking_pool.as_mut(),
+        accounts.pool_token_account.as_ref(),
+        &accounts.token_program,
+        revoke_at,
+        leaver_policy,
+    )
+}
+
+/// Revokes a schedule under the given leaver policy. Shared by company
+/// revocation and revocation triggered by an employee's termination.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_revocation<'info>(
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    company: &mut Account<'info, Company>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vesting_vault: &InterfaceAccount<'info, TokenAccount>,
+    company_token_account: &InterfaceAccount<'info, TokenAccount>,
+    beneficiary_token_account: &InterfaceAccount<'info, TokenAccount>,
+    performance_metric: Option<&Account<'info, PerformanceMetric>>,
+    staking_pool: Option<&mut Account<'info, StakingPool>>,
+    pool_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    revoke_at: i64,
+    leaver_policy: LeaverPolicy,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(
+        revoke_at <= clock.unix_timestamp,
+        BankingVestingError::InvalidTimestamp
+    );
+    
+    if let Some(performance_metric) = performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    if vesting_schedule.staked_amount > 0 {
+        let (Some(staking_pool), Some(pool_token_account)) = (staking_pool, pool_token_account) else {
+            return Err(BankingVestingError::EscrowStillStaked.into());
+        };
+        
+        // Rewards earned so far stay claimable by the beneficiary after revocation
+        accrue_stake_rewards(vesting_schedule, staking_pool.apy_rate, clock.unix_timestamp)?;
+        
+        let staked_amount = vesting_schedule.staked_amount;
+        
+        transfer_from_staking_pool(
+            staking_pool,
+            mint,
+            pool_token_account.to_account_info(),
+            vesting_vault.to_account_info(),
+            token_program.to_account_info(),
+            staked_amount,
+        )?;
+        
+        staking_pool.total_staked = staking_pool.total_staked
+            .checked_sub(staked_amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        vesting_schedule.staked_amount = 0;
+        
+        emit!(VestingEscrowUnstaked {
+            schedule: vesting_schedule.key(),
+            pool: staking_pool.key(),
+            beneficiary: vesting_schedule.beneficiary,
+            amount: staked_amount,
+            staked_amount: 0,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // Second trigger: let go without cause within the window after a change of control
+    let change_of_control_at = company.change_of_control_at;
+    if vesting_schedule.acceleration_policy.trigger == AccelerationTrigger::DoubleTrigger
+        && vesting_schedule.accelerated_at == 0
+        && leaver_policy != LeaverPolicy::BadLeaver
+        && change_of_control_at != 0
+        && vesting_schedule.created_at <= change_of_control_at
+        && revoke_at >= change_of_control_at
+        && revoke_at - change_of_control_at <= DOUBLE_TRIGGER_WINDOW
+    {
+        let accelerated_amount = apply_acceleration(vesting_schedule, revoke_at)?;
+        
+        emit!(VestingAccelerated {
+            schedule: vesting_schedule.key(),
+            company: vesting_schedule.company,
+            beneficiary: vesting_schedule.beneficiary,
+            trigger: acceleration_trigger_str(AccelerationTrigger::DoubleTrigger).to_string(),
+            accelerated_amount,
+            change_of_control_at,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    // Freeze vesting at the revocation time, never below what was already claimed
+    let vested_amount = calculate_schedule_vested_amount(vesting_schedule, revoke_at)?
+        .max(vesting_schedule.claimed_amount);
+    
+    let unclaimed_vested = vested_amount
+        .checked_sub(vesting_schedule.claimed_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    let is_option_grant = vesting_schedule.option_terms.payment_mint != Pubkey::default();
+    
+    let (retained_amount, paid_out) = match leaver_policy {
+        // Options can't be handed over without the strike, they stay exercisable in escrow
+        LeaverPolicy::GoodLeaver if is_option_grant => (vested_amount, 0),
+        LeaverPolicy::GoodLeaver => (vested_amount, unclaimed_vested),
+        LeaverPolicy::GoodLeaverDeferred => (vested_amount, 0),
+        LeaverPolicy::BadLeaver => (vesting_schedule.claimed_amount, 0),
+    };
+    
+    let returned_amount = vesting_schedule.total_amount
+        .checked_sub(retained_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    let beneficiary_key = vesting_schedule.beneficiary;
+    
+    if paid_out > 0 {
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            mint,
+            vesting_vault.to_account_info(),
+            beneficiary_token_account.to_account_info(),
+            token_program.to_account_info(),
+            paid_out,
+        )?;
+        
+        vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
+            .checked_add(paid_out)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        vesting_schedule.last_claimed = clock.unix_timestamp;
+    }
+    
+    if returned_amount > 0 {
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            mint,
+            vesting_vault.to_account_info(),
+            company_token_account.to_account_info(),
+            token_program.to_account_info(),
+            returned_amount,
+        )?;
+    }
+    
+    vesting_schedule.total_amount = retained_amount;
+    vesting_schedule.is_revoked = true;
+    vesting_schedule.revoked_at = revoke_at;
+    
+    company.allocated_supply = company.allocated_supply
+        .checked_sub(returned_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_sub(paid_out)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    emit!(VestingScheduleRevoked {
+        schedule: vesting_schedule.key(),
+        company: company.key(),
+        beneficiary: beneficiary_key,
+        leaver_policy: leaver_policy_str(leaver_policy).to_string(),
+        vested_amount,
+        paid_out,
+        returned_amount,
+        revoked_at: revoke_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn amend_vesting_schedule(
+    ctx: Context<AmendVestingSchedule>,
+    total_amount: u64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+) -> Result<()> {
+    validate_vesting_durations(cliff_duration, vesting_duration)?;
+    
+    // Any change of terms needs the signer set, not only top-ups
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    // Top-ups are held to the same approval bar as new grants
+    if total_amount > ctx.accounts.vesting_schedule.total_amount {
+        require_grant_approval(
+            &ctx.accounts.company,
+            &ctx.accounts.authority.key(),
+            total_amount,
+            ctx.remaining_accounts,
+        )?;
+    }
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    
+    validate_release_terms(vesting_schedule.vesting_type, &vesting_schedule.release_terms, vesting_duration)?;
+    
+    require!(
+        total_amount > 0,
+        BankingVestingError::InvalidAmount
+    );
+    
+    let clock = Clock::get()?;
+    
+    require_performance_metric(vesting_schedule, ctx.accounts.performance_metric.as_ref())?;
+    
+    if let Some(performance_metric) = &ctx.accounts.performance_metric {
+        refresh_performance_unlock(vesting_schedule, performance_metric.peak_value);
+    }
+    
+    let vested_before = calculate_schedule_vested_amount(vesting_schedule, clock.unix_timestamp)?
+        .max(vesting_schedule.claimed_amount);
+    
+    let previous_total_amount = vesting_schedule.total_amount;
+    vesting_schedule.total_amount = total_amount;
+    vesting_schedule.cliff_duration = cliff_duration;
+    vesting_schedule.vesting_duration = vesting_duration;
+    
+    let vested_after = calculate_schedule_vested_amount(vesting_schedule, clock.unix_timestamp)?;
+    
+    // New terms may only be as generous or more for what has already vested
+    require!(
+        vested_after >= vested_before,
+        BankingVestingError::AmendmentReducesVested
+    );
+    
+    let company = &mut ctx.accounts.company;
+    
+    if total_amount > previous_total_amount {
+        let top_up = total_amount - previous_total_amount;
+        let funding_amount = calculate_gross_transfer_amount(&ctx.accounts.mint, top_up)?;
+        
+        require!(
+            ctx.accounts.company_token_account.amount >= funding_amount,
+            BankingVestingError::InsufficientBalance
+        );
+        
+        let company_creator = company.creator;
+        let company_name = company.name;
+        let seeds = &[
+            COMPANY_SEED,
+            company_creator.as_ref(),
+            &company_name,
+            &[company.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.company_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vesting_vault.to_account_info(),
+                authority: company.to_account_info(),
+            },
+            signer_seeds,
+        );
+        
+        transfer_checked(transfer_ctx, funding_amount, ctx.accounts.mint.decimals)?;
+        
+        company.allocated_supply = company.allocated_supply
+            .checked_add(top_up)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    } else if total_amount < previous_total_amount {
+        let released = previous_total_amount - total_amount;
+        
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            &ctx.accounts.mint,
+            ctx.accounts.vesting_vault.to_account_info(),
+            ctx.accounts.company_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            released,
+        )?;
+        
+        company.allocated_supply = company.allocated_supply
+            .checked_sub(released)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    }
+    
+    vesting_schedule.amendment_count = vesting_schedule.amendment_count
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    vesting_schedule.last_amended = clock.unix_timestamp;
+    
+    emit!(VestingScheduleAmended {
+        schedule: vesting_schedule.key(),
+        company: company.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        previous_total_amount,
+        total_amount,
+        cliff_duration,
+        vesting_duration,
+        amendment_count: vesting_schedule.amendment_count,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Closes a settled schedule together with its vault and any milestones passed
+/// as remaining accounts, returning all rent to the company authority.
+pub fn close_vesting_schedule<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseVestingSchedule<'info>>,
+) -> Result<()> {
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    let schedule_key = vesting_schedule.key();
+    let clock = Clock::get()?;
+    
+    let mut milestones_closed: u8 = 0;
+    for milestone_info in ctx.remaining_accounts.iter() {
+        let milestone = Account::<Milestone>::try_from(milestone_info)?;
+        
+        require_keys_eq!(milestone.schedule, schedule_key, BankingVestingError::Unauthorized);
+        
+        milestone.close(ctx.accounts.authority.to_account_info())?;
+        
+        milestones_closed = milestones_closed
+            .checked_add(1)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    }
+    
+    let stray_amount = ctx.accounts.vesting_vault.amount;
+    if stray_amount > 0 {
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            &ctx.accounts.mint,
+            ctx.accounts.vesting_vault.to_account_info(),
+            ctx.accounts.company_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            stray_amount,
+        )?;
+    }
+    
+    let grant_id_bytes = vesting_schedule.grant_id.to_le_bytes();
     let seeds = &[
-        COMPANY_SEED,
-        company_authority.as_ref(),
-        company_name,
-        &[ctx.accounts.company.bump],
+        VESTING_SEED,
+        vesting_schedule.company.as_ref(),
+        vesting_schedule.beneficiary.as_ref(),
+        &grant_id_bytes,
+        &[vesting_schedule.bump],
     ];
     let signer_seeds = &[&seeds[..]];
     
-    let transfer_ctx = CpiContext::new_with_signer(
+    let close_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.company_token_account.to_account_info(),
-            to: ctx.accounts.beneficiary_token_account.to_account_info(),
-            authority: ctx.accounts.company.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vesting_vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: vesting_schedule.to_account_info(),
         },
         signer_seeds,
     );
     
-    transfer(transfer_ctx, claimable_amount)?;
+    close_account(close_ctx)?;
     
-    vesting_schedule.claimed_amount = vesting_schedule.claimed_amount
-        .checked_add(claimable_amount)
+    let beneficiary_grants = &mut ctx.accounts.beneficiary_grants;
+    beneficiary_grants.active_grants = beneficiary_grants.active_grants
+        .checked_sub(1)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
-    vesting_schedule.last_claimed = clock.unix_timestamp;
+    let company = &mut ctx.accounts.company;
+    company.vesting_schedules_count = company.vesting_schedules_count
+        .checked_sub(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
-    emit!(TokensClaimed {
-        schedule: vesting_schedule.key(),
-        beneficiary: ctx.accounts.beneficiary.key(),
-        amount: claimable_amount,
-        total_claimed: vesting_schedule.claimed_amount,
+    emit!(VestingScheduleClosed {
+        schedule: schedule_key,
+        company: company.key(),
+        beneficiary: vesting_schedule.beneficiary,
+        total_amount: vesting_schedule.total_amount,
+        milestones_closed,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Re-points a schedule to a new beneficiary. The schedule and its vault are
+/// migrated to the new owner's PDAs; milestones of the old schedule must be
+/// passed as remaining accounts so they follow it.
+pub fn transfer_vesting_schedule<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TransferVestingSchedule<'info>>,
+) -> Result<()> {
+    let company = &ctx.accounts.company;
+    
+    if company.require_transfer_approval {
+        require!(
+            ctx.accounts.company_authority.is_some(),
+            BankingVestingError::TransferApprovalRequired
+        );
+    }
+    
+    let new_beneficiary_key = ctx.accounts.new_beneficiary.key();
+    let new_beneficiary_grants = &mut ctx.accounts.new_beneficiary_grants;
+    
+    require!(
+        (new_beneficiary_grants.active_grants as usize) < MAX_VESTING_SCHEDULES_PER_USER,
+        BankingVestingError::TooManyVestingSchedules
+    );
+    
+    if new_beneficiary_grants.company == Pubkey::default() {
+        new_beneficiary_grants.company = company.key();
+        new_beneficiary_grants.beneficiary = new_beneficiary_key;
+        new_beneficiary_grants.grant_count = 0;
+        new_beneficiary_grants.active_grants = 0;
+        new_beneficiary_grants.bump = ctx.bumps.new_beneficiary_grants;
+    }
+    
+    let new_grant_id = new_beneficiary_grants.grant_count;
+    
+    new_beneficiary_grants.grant_count = new_beneficiary_grants.grant_count
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    new_beneficiary_grants.active_grants = new_beneficiary_grants.active_grants
+        .checked_add(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    let beneficiary_grants = &mut ctx.accounts.beneficiary_grants;
+    beneficiary_grants.active_grants = beneficiary_grants.active_grants
+        .checked_sub(1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    // The grant leaves the employee it was linked to
+    if ctx.accounts.vesting_schedule.employee != Pubkey::default() {
+        let employee = ctx.accounts.employee
+            .as_mut()
+            .ok_or(BankingVestingError::AccountNotFound)?;
+        
+        employee.schedules_linked = employee.schedules_linked
+            .checked_sub(1)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    }
+    
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    let old_schedule_key = vesting_schedule.key();
+    let new_schedule_key = ctx.accounts.new_vesting_schedule.key();
+    
+    // Every milestone has to follow the schedule, otherwise its tranche is stranded
+    require!(
+        ctx.remaining_accounts.len() == vesting_schedule.milestone_count as usize,
+        BankingVestingError::AccountNotFound
+    );
+    
+    for (index, milestone_info) in ctx.remaining_accounts.iter().enumerate() {
+        let mut milestone = Account::<Milestone>::try_from(milestone_info)?;
+        
+        require!(
+            milestone.schedule == old_schedule_key && milestone.index as usize == index,
+            BankingVestingError::Unauthorized
+        );
+        
+        milestone.schedule = new_schedule_key;
+        milestone.exit(ctx.program_id)?;
+    }
+    
+    let new_vesting_schedule = &mut ctx.accounts.new_vesting_schedule;
+    let clock = Clock::get()?;
+    
+    new_vesting_schedule.set_inner(VestingSchedule {
+        beneficiary: new_beneficiary_key,
+        grant_id: new_grant_id,
+        bump: ctx.bumps.new_vesting_schedule,
+        // Claim settings were chosen by the previous owner
+        claim_delegate: Pubkey::default(),
+        permissionless_claims: false,
+        crank_tip_bps: 0,
+        // The new holder is not the employee the grant was linked to. Withholding
+        // carries over unchanged, only the beneficiary can agree to a higher rate
+        employee: Pubkey::default(),
+        ..(**vesting_schedule).clone()
+    });
+    
+    let amount_moved = ctx.accounts.vesting_vault.amount;
+    
+    // The new vault has to receive everything the grant still owes
+    require!(
+        amount_moved == 0 || !has_transfer_fee(&ctx.accounts.mint)?,
+        BankingVestingError::TransferFeeNotSupported
+    );
+    
+    if amount_moved > 0 {
+        transfer_from_vesting_vault(
+            vesting_schedule,
+            &ctx.accounts.mint,
+            ctx.accounts.vesting_vault.to_account_info(),
+            ctx.accounts.new_vesting_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount_moved,
+        )?;
+    }
+    
+    // The emptied vault goes away with the old schedule, rent back to the beneficiary
+    let grant_id_bytes = vesting_schedule.grant_id.to_le_bytes();
+    let seeds = &[
+        VESTING_SEED,
+        vesting_schedule.company.as_ref(),
+        vesting_schedule.beneficiary.as_ref(),
+        &grant_id_bytes,
+        &[vesting_schedule.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vesting_vault.to_account_info(),
+            destination: ctx.accounts.beneficiary.to_account_info(),
+            authority: vesting_schedule.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    close_account(close_ctx)?;
+    
+    emit!(VestingScheduleTransferred {
+        old_schedule: old_schedule_key,
+        new_schedule: new_schedule_key,
+        company: company.key(),
+        old_beneficiary: ctx.accounts.beneficiary.key(),
+        new_beneficiary: new_beneficiary_key,
+        amount_moved,
+        approved_by: ctx.accounts.company_authority.as_ref().map(|authority| authority.key()),
         timestamp: clock.unix_timestamp,
     });
     
     Ok(())
 }
+
+/// Sends the withheld share of a claim to the withholding destination and
+/// returns how much was withheld
+pub fn transfer_withholding<'info>(
+    vesting_schedule: &Account<'info, VestingSchedule>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vesting_vault: AccountInfo<'info>,
+    withholding_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: AccountInfo<'info>,
+    withholding: &WithholdingTerms,
+    claimable_amount: u64,
+) -> Result<u64> {
+    let withheld_amount = calculate_withholding_amount(claimable_amount, withholding.withholding_bps)?;
+    
+    if withheld_amount == 0 {
+        return Ok(0);
+    }
+    
+    let withholding_token_account = withholding_token_account
+        .ok_or(BankingVestingError::AccountNotFound)?;
+    
+    require_keys_eq!(
+        withholding_token_account.key(),
+        withholding.destination,
+        BankingVestingError::InvalidWithholdingAccount
+    );
+    
+    transfer_from_vesting_vault(
+        vesting_schedule,
+        mint,
+        vesting_vault,
+        withholding_token_account.to_account_info(),
+        token_program,
+        withheld_amount,
+    )?;
+    
+    Ok(withheld_amount)
+}
+
+/// Moves `amount` out of a schedule's escrow vault, signing as the schedule PDA
+pub fn transfer_from_vesting_vault<'info>(
+    vesting_schedule: &Account<'info, VestingSchedule>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vesting_vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    // Staked principal sits in the pool, not the vault, until it is unstaked
+    if vesting_schedule.staked_amount > 0 {
+        let vault_amount = {
+            let vault_data = vesting_vault.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &vault_data[..])?.amount
+        };
+        
+        require!(
+            amount <= vault_amount,
+            BankingVestingError::TokensStaked
+        );
+    }
+    
+    let grant_id_bytes = vesting_schedule.grant_id.to_le_bytes();
+    let seeds = &[
+        VESTING_SEED,
+        vesting_schedule.company.as_ref(),
+        vesting_schedule.beneficiary.as_ref(),
+        &grant_id_bytes,
+        &[vesting_schedule.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        TransferChecked {
+            from: vesting_vault,
+            mint: mint.to_account_info(),
+            to: destination,
+            authority: vesting_schedule.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, amount, mint.decimals)
+}
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::errors::BankingVestingError;
+use crate::utils::*;
+
+#[derive(Accounts)]
+pub struct SetCompanyWithholding<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        constraint = mint.key() == company.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Payroll PDA, only ever signs for the payroll token account
+    #[account(
+        seeds = [PAYROLL_SEED, company.key().as_ref()],
+        bump
+    )]
+    pub payroll: UncheckedAccount<'info>,
+    
+    /// Withheld tokens can only ever go here
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = payroll,
+        associated_token::token_program = token_program
+    )]
+    pub payroll_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetScheduleWithholding<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            company.key().as_ref(),
+            vesting_schedule.beneficiary.as_ref(),
+            &vesting_schedule.grant_id.to_le_bytes()
+        ],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ BankingVestingError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Payroll PDA, only ever signs for the payroll token account
+    #[account(
+        seeds = [PAYROLL_SEED, company.key().as_ref()],
+        bump
+    )]
+    pub payroll: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = payroll,
+        associated_token::token_program = token_program
+    )]
+    pub payroll_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Only needed when the withheld share goes up
+    #[account(
+        constraint = beneficiary.key() == vesting_schedule.beneficiary @ BankingVestingError::Unauthorized
+    )]
+    pub beneficiary: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemitWithholding<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.creator.as_ref(), &company.name],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ BankingVestingError::Unauthorized
+    )]
+    pub company: Account<'info, Company>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Payroll PDA, signs the remittance
+    #[account(
+        seeds = [PAYROLL_SEED, company.key().as_ref()],
+        bump
+    )]
+    pub payroll: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payroll,
+        associated_token::token_program = token_program
+    )]
+    pub payroll_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sets the withholding new grants of the company start with. Existing grants
+/// keep the terms they were created with.
+pub fn set_company_withholding(
+    ctx: Context<SetCompanyWithholding>,
+    withholding: WithholdingTerms,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    validate_withholding_terms(&withholding, &ctx.accounts.payroll_token_account.key())?;
+    
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    
+    company.withholding = withholding;
+    
+    emit!(WithholdingTermsUpdated {
+        company: company.key(),
+        schedule: None,
+        withholding_bps: withholding.withholding_bps,
+        destination: withholding.destination,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Changes the withholding of one grant. The company can lower it on its own,
+/// raising it takes the beneficiary's signature as well.
+pub fn set_schedule_withholding(
+    ctx: Context<SetScheduleWithholding>,
+    withholding: WithholdingTerms,
+) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    validate_withholding_terms(&withholding, &ctx.accounts.payroll_token_account.key())?;
+    
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    let clock = Clock::get()?;
+    
+    require!(
+        withholding.withholding_bps <= vesting_schedule.withholding.withholding_bps
+            || ctx.accounts.beneficiary.is_some(),
+        BankingVestingError::Unauthorized
+    );
+    
+    vesting_schedule.withholding = withholding;
+    
+    emit!(WithholdingTermsUpdated {
+        company: ctx.accounts.company.key(),
+        schedule: Some(vesting_schedule.key()),
+        withholding_bps: withholding.withholding_bps,
+        destination: withholding.destination,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Pays withheld tokens out of the payroll account, e.g. to the tax authority
+pub fn remit_withholding(ctx: Context<RemitWithholding>, amount: u64) -> Result<()> {
+    require_signer_approval(
+        &ctx.accounts.company,
+        &ctx.accounts.authority.key(),
+        ctx.remaining_accounts,
+    )?;
+    
+    require!(
+        amount > 0 && amount <= ctx.accounts.payroll_token_account.amount,
+        BankingVestingError::InvalidAmount
+    );
+    
+    let clock = Clock::get()?;
+    
+    let company_key = ctx.accounts.company.key();
+    let seeds = &[
+        PAYROLL_SEED,
+        company_key.as_ref(),
+        &[ctx.bumps.payroll],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.payroll_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.payroll.to_account_info(),
+        },
+        signer_seeds,
+    );
+    
+    transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    
+    emit!(WithholdingRemitted {
+        company: company_key,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
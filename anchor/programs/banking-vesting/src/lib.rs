@@ -6,9 +6,14 @@ pub mod errors;
 pub mod events;
 pub mod constants;
 pub mod utils;
+pub mod merkle;
 
 use instructions::*;
-use state::VestingType;
+use merkle::DistributorLeaf;
+use state::{
+    AccelerationPolicy, EmployeeStatus, LeaverPolicy, OptionTerms, PerformanceBand, ReleaseTerms, TemplateTerms, VestingType,
+    SupplyPolicy, WithholdingTerms,
+};
 
 declare_id!("B5woVTwykhf4P4MHHawK3GgbHZBTT9Yvig426omjikB1");
 
@@ -29,10 +34,83 @@ pub mod banking_vesting {
         name: String,
         symbol: String,
         total_supply: u64,
+        supply_policy: SupplyPolicy,
     ) -> Result<()> {
-        instructions::create_company(ctx, name, symbol, total_supply)
+        instructions::create_company(ctx, name, symbol, total_supply, supply_policy)
     }
 
+    pub fn register_company_with_mint(
+        ctx: Context<RegisterCompanyWithMint>,
+        name: String,
+        symbol: String,
+    ) -> Result<()> {
+        instructions::register_company_with_mint(ctx, name, symbol)
+    }
+
+    pub fn mint_additional_supply(ctx: Context<MintAdditionalSupply>, amount: u64) -> Result<()> {
+        instructions::mint_additional_supply(ctx, amount)
+    }
+
+    pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthority>) -> Result<()> {
+        instructions::revoke_mint_authority(ctx)
+    }
+
+    pub fn propose_company_authority(
+        ctx: Context<ProposeCompanyAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_company_authority(ctx, new_authority)
+    }
+
+    pub fn accept_company_authority(ctx: Context<AcceptCompanyAuthority>) -> Result<()> {
+        instructions::accept_company_authority(ctx)
+    }
+
+    pub fn set_company_signers(
+        ctx: Context<SetCompanySigners>,
+        signers: Vec<Pubkey>,
+        approval_threshold: u8,
+        grant_approval_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_company_signers(ctx, signers, approval_threshold, grant_approval_threshold)
+    }
+
+    pub fn onboard_employee(
+        ctx: Context<OnboardEmployee>,
+        wallet: Pubkey,
+        department: String,
+        role: String,
+        hire_date: i64,
+    ) -> Result<()> {
+        instructions::onboard_employee(ctx, wallet, department, role, hire_date)
+    }
+
+    pub fn update_employee(
+        ctx: Context<UpdateEmployee>,
+        department: String,
+        role: String,
+        status: EmployeeStatus,
+    ) -> Result<()> {
+        instructions::update_employee(ctx, department, role, status)
+    }
+
+    pub fn link_employee_schedule(ctx: Context<LinkEmployeeSchedule>) -> Result<()> {
+        instructions::link_employee_schedule(ctx)
+    }
+
+    pub fn offboard_employee(
+        ctx: Context<OffboardEmployee>,
+        termination_date: i64,
+        leaver_policy: Option<LeaverPolicy>,
+    ) -> Result<()> {
+        instructions::offboard_employee(ctx, termination_date, leaver_policy)
+    }
+
+    pub fn revoke_terminated_schedule(ctx: Context<RevokeTerminatedSchedule>) -> Result<()> {
+        instructions::revoke_terminated_schedule(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_vesting_schedule(
         ctx: Context<CreateVestingSchedule>,
         beneficiary: Pubkey,
@@ -41,6 +119,7 @@ pub mod banking_vesting {
         cliff_duration: i64,
         vesting_duration: i64,
         vesting_type: VestingType,
+        release_terms: ReleaseTerms,
     ) -> Result<()> {
         instructions::create_vesting_schedule(
             ctx,
@@ -50,6 +129,35 @@ pub mod banking_vesting {
             cliff_duration,
             vesting_duration,
             vesting_type,
+            release_terms,
+        )
+    }
+
+    pub fn create_vesting_template(
+        ctx: Context<CreateVestingTemplate>,
+        template_id: u32,
+        terms: TemplateTerms,
+    ) -> Result<()> {
+        instructions::create_vesting_template(ctx, template_id, terms)
+    }
+
+    pub fn update_vesting_template(ctx: Context<UpdateVestingTemplate>, terms: TemplateTerms) -> Result<()> {
+        instructions::update_vesting_template(ctx, terms)
+    }
+
+    pub fn create_vesting_schedule_from_template(
+        ctx: Context<CreateVestingScheduleFromTemplate>,
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_time: i64,
+        template_version: u32,
+    ) -> Result<()> {
+        instructions::create_vesting_schedule_from_template(
+            ctx,
+            beneficiary,
+            total_amount,
+            start_time,
+            template_version,
         )
     }
 
@@ -57,6 +165,189 @@ pub mod banking_vesting {
         instructions::claim_vested_tokens(ctx)
     }
 
+    pub fn claim_all_vested_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAllVestedTokens<'info>>,
+    ) -> Result<()> {
+        instructions::claim_all_vested_tokens(ctx)
+    }
+
+    pub fn revoke_vesting_schedule(
+        ctx: Context<RevokeVestingSchedule>,
+        revoke_at: i64,
+        leaver_policy: LeaverPolicy,
+    ) -> Result<()> {
+        instructions::revoke_vesting_schedule(ctx, revoke_at, leaver_policy)
+    }
+
+    pub fn amend_vesting_schedule(
+        ctx: Context<AmendVestingSchedule>,
+        total_amount: u64,
+        cliff_duration: i64,
+        vesting_duration: i64,
+    ) -> Result<()> {
+        instructions::amend_vesting_schedule(ctx, total_amount, cliff_duration, vesting_duration)
+    }
+
+    pub fn pause_vesting_schedule(ctx: Context<PauseVestingSchedule>) -> Result<()> {
+        instructions::pause_vesting_schedule(ctx)
+    }
+
+    pub fn resume_vesting_schedule(ctx: Context<ResumeVestingSchedule>) -> Result<()> {
+        instructions::resume_vesting_schedule(ctx)
+    }
+
+    pub fn transfer_vesting_schedule<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TransferVestingSchedule<'info>>,
+    ) -> Result<()> {
+        instructions::transfer_vesting_schedule(ctx)
+    }
+
+    pub fn set_transfer_approval_policy(
+        ctx: Context<SetTransferApprovalPolicy>,
+        require_approval: bool,
+    ) -> Result<()> {
+        instructions::set_transfer_approval_policy(ctx, require_approval)
+    }
+
+    pub fn set_claim_settings(
+        ctx: Context<SetClaimSettings>,
+        claim_delegate: Pubkey,
+        permissionless_claims: bool,
+        crank_tip_bps: u16,
+    ) -> Result<()> {
+        instructions::set_claim_settings(ctx, claim_delegate, permissionless_claims, crank_tip_bps)
+    }
+
+    pub fn crank_claim_vested_tokens(ctx: Context<CrankClaimVestedTokens>) -> Result<()> {
+        instructions::crank_claim_vested_tokens(ctx)
+    }
+
+    pub fn declare_change_of_control(ctx: Context<DeclareChangeOfControl>) -> Result<()> {
+        instructions::declare_change_of_control(ctx)
+    }
+
+    pub fn set_acceleration_policy(
+        ctx: Context<SetAccelerationPolicy>,
+        acceleration_policy: AccelerationPolicy,
+    ) -> Result<()> {
+        instructions::set_acceleration_policy(ctx, acceleration_policy)
+    }
+
+    pub fn accelerate_vesting_schedule(ctx: Context<AccelerateVestingSchedule>) -> Result<()> {
+        instructions::accelerate_vesting_schedule(ctx)
+    }
+
+    pub fn set_option_terms(ctx: Context<SetOptionTerms>, option_terms: OptionTerms) -> Result<()> {
+        instructions::set_option_terms(ctx, option_terms)
+    }
+
+    pub fn exercise_vested_options(ctx: Context<ExerciseVestedOptions>, quantity: u64) -> Result<()> {
+        instructions::exercise_vested_options(ctx, quantity)
+    }
+
+    pub fn reclaim_expired_options(ctx: Context<ReclaimExpiredOptions>) -> Result<()> {
+        instructions::reclaim_expired_options(ctx)
+    }
+
+    pub fn close_vesting_schedule<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseVestingSchedule<'info>>,
+    ) -> Result<()> {
+        instructions::close_vesting_schedule(ctx)
+    }
+
+    pub fn quote_vesting_schedule(
+        ctx: Context<QuoteVestingSchedule>,
+        timestamp: Option<i64>,
+    ) -> Result<VestingQuote> {
+        instructions::quote_vesting_schedule(ctx, timestamp)
+    }
+
+    pub fn add_vesting_milestone(
+        ctx: Context<AddVestingMilestone>,
+        weight_bps: u16,
+        attester: Pubkey,
+    ) -> Result<()> {
+        instructions::add_vesting_milestone(ctx, weight_bps, attester)
+    }
+
+    pub fn achieve_vesting_milestone(ctx: Context<AchieveVestingMilestone>) -> Result<()> {
+        instructions::achieve_vesting_milestone(ctx)
+    }
+
+    pub fn create_performance_metric(
+        ctx: Context<CreatePerformanceMetric>,
+        metric_id: u32,
+        reporter: Pubkey,
+    ) -> Result<()> {
+        instructions::create_performance_metric(ctx, metric_id, reporter)
+    }
+
+    pub fn report_performance_metric(
+        ctx: Context<ReportPerformanceMetric>,
+        value: u64,
+    ) -> Result<()> {
+        instructions::report_performance_metric(ctx, value)
+    }
+
+    pub fn set_performance_target(
+        ctx: Context<SetPerformanceTarget>,
+        bands: Vec<PerformanceBand>,
+    ) -> Result<()> {
+        instructions::set_performance_target(ctx, bands)
+    }
+
+    pub fn create_merkle_distributor(
+        ctx: Context<CreateMerkleDistributor>,
+        distributor_id: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_merkle_distributor(ctx, distributor_id, merkle_root, total_amount, expires_at)
+    }
+
+    pub fn clawback_distributor(ctx: Context<ClawbackDistributor>) -> Result<()> {
+        instructions::clawback_distributor(ctx)
+    }
+
+    pub fn claim_from_distributor(
+        ctx: Context<ClaimFromDistributor>,
+        leaf: DistributorLeaf,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_from_distributor(ctx, leaf, proof)
+    }
+
+    pub fn stake_vesting_escrow(ctx: Context<StakeVestingEscrow>, amount: u64) -> Result<()> {
+        instructions::stake_vesting_escrow(ctx, amount)
+    }
+
+    pub fn unstake_vesting_escrow(ctx: Context<UnstakeVestingEscrow>, amount: u64) -> Result<()> {
+        instructions::unstake_vesting_escrow(ctx, amount)
+    }
+
+    pub fn claim_stake_rewards(ctx: Context<ClaimStakeRewards>) -> Result<()> {
+        instructions::claim_stake_rewards(ctx)
+    }
+
+    pub fn set_company_withholding(
+        ctx: Context<SetCompanyWithholding>,
+        withholding: WithholdingTerms,
+    ) -> Result<()> {
+        instructions::set_company_withholding(ctx, withholding)
+    }
+
+    pub fn set_schedule_withholding(
+        ctx: Context<SetScheduleWithholding>,
+        withholding: WithholdingTerms,
+    ) -> Result<()> {
+        instructions::set_schedule_withholding(ctx, withholding)
+    }
+
+    pub fn remit_withholding(ctx: Context<RemitWithholding>, amount: u64) -> Result<()> {
+        instructions::remit_withholding(ctx, amount)
+    }
+
     pub fn deposit_funds(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
         instructions::deposit_funds(ctx, amount)
     }
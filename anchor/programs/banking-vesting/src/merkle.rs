@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// Domain separation so an inner node can never be replayed as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// One recipient entry of a merkle distributor
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DistributorLeaf {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+impl DistributorLeaf {
+    pub fn hash(&self) -> [u8; 32] {
+        hashv(&[
+            LEAF_PREFIX,
+            self.beneficiary.as_ref(),
+            &self.amount.to_le_bytes(),
+            &self.start_time.to_le_bytes(),
+            &self.cliff_duration.to_le_bytes(),
+            &self.vesting_duration.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// Sorted pair hashing, so proofs don't need to carry left/right positions
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    
+    computed == *root
+}
+
+/// Off-chain helper for companies publishing a distributor: builds the tree
+/// over the recipient list and produces the proof each recipient submits.
+/// Not compiled into the on-chain program.
+#[cfg(not(target_os = "solana"))]
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl MerkleTree {
+    pub fn new(leaves: &[DistributorLeaf]) -> Self {
+        let mut layers = vec![leaves.iter().map(DistributorLeaf::hash).collect::<Vec<_>>()];
+        
+        while layers[layers.len() - 1].len() > 1 {
+            let next = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    // An odd node is promoted unchanged to the next layer
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        
+        Self { layers }
+    }
+    
+    pub fn root(&self) -> [u8; 32] {
+        self.layers[self.layers.len() - 1]
+            .first()
+            .copied()
+            .unwrap_or_default()
+    }
+    
+    pub fn proof(&self, index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+        
+        let mut proof = Vec::with_capacity(self.layers.len());
+        let mut position = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn leaves(count: usize) -> Vec<DistributorLeaf> {
+        (0..count)
+            .map(|index| DistributorLeaf {
+                beneficiary: Pubkey::new_unique(),
+                amount: 1_000 * (index as u64 + 1),
+                start_time: 1_700_000_000,
+                cliff_duration: 0,
+                vesting_duration: 86_400,
+            })
+            .collect()
+    }
+    
+    #[test]
+    fn proofs_verify_against_root() {
+        for count in [1, 2, 3, 4, 5, 7, 8, 9] {
+            let leaves = leaves(count);
+            let tree = MerkleTree::new(&leaves);
+            let root = tree.root();
+            
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(
+                    verify_merkle_proof(&proof, &root, leaf.hash()),
+                    "leaf {index} of {count} failed to verify"
+                );
+            }
+            
+            assert!(tree.proof(count).is_none());
+        }
+    }
+    
+    #[test]
+    fn tampered_leaf_is_rejected() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root();
+        
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            let tampered = DistributorLeaf {
+                amount: leaf.amount + 1,
+                ..*leaf
+            };
+            assert!(!verify_merkle_proof(&proof, &root, tampered.hash()));
+        }
+    }
+    
+    #[test]
+    fn proof_for_another_leaf_is_rejected() {
+        let leaves = leaves(4);
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root();
+        
+        let proof = tree.proof(0).unwrap();
+        assert!(!verify_merkle_proof(&proof, &root, leaves[1].hash()));
+    }
+}
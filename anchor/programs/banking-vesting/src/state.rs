@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::{MAX_COMPANY_SIGNERS, MAX_PERFORMANCE_BANDS};
 
 #[account]
 pub struct Platform {
@@ -15,13 +16,25 @@ pub struct Platform {
 #[account]
 pub struct Company {
     pub authority: Pubkey,
+    pub creator: Pubkey, // PDA seed, stays fixed when the authority rotates
+    pub pending_authority: Pubkey, // Default unless a transfer is awaiting acceptance
     pub name: [u8; 32], 
     pub symbol: [u8; 8], 
     pub mint: Pubkey,
-    pub total_supply: u64,
+    pub total_supply: u64, // Declared supply, not necessarily minted yet
+    pub issued_supply: u64, // Actually minted, what the supply cap is checked against
     pub allocated_supply: u64,
     pub employees_count: u64,
     pub vesting_schedules_count: u64,
+    pub require_transfer_approval: bool, // Beneficiary transfers need authority co-sign
+    pub change_of_control_at: i64, // 0 until a change of control is declared
+    pub withholding: WithholdingTerms, // Copied onto every new grant
+    pub supply_cap: u64, // Most that issued_supply can ever grow to
+    pub mint_authority_retained: bool,
+    pub signer_set: [Pubkey; MAX_COMPANY_SIGNERS],
+    pub signer_count: u8,
+    pub approval_threshold: u8, // Signers needed for sensitive actions, 0 disables the signer set
+    pub grant_approval_threshold: u64, // Grants larger than this need the signer set's approval
     pub created_at: i64,
     pub bump: u8,
 }
@@ -30,6 +43,7 @@ pub struct Company {
 pub struct VestingSchedule {
     pub company: Pubkey,
     pub beneficiary: Pubkey,
+    pub grant_id: u64,
     pub mint: Pubkey,
     pub total_amount: u64,
     pub claimed_amount: u64,
@@ -37,12 +51,130 @@ pub struct VestingSchedule {
     pub cliff_duration: i64,
     pub vesting_duration: i64,
     pub vesting_type: VestingType,
+    pub release_terms: ReleaseTerms,
     pub is_revoked: bool,
+    pub revoked_at: i64,
+    pub milestone_count: u8,
+    pub milestone_total_bps: u16,
+    pub milestone_achieved_bps: u16,
+    pub performance_metric: Pubkey,
+    pub performance_bands: [PerformanceBand; MAX_PERFORMANCE_BANDS],
+    pub performance_band_count: u8,
+    pub performance_unlocked_bps: u16,
+    pub claim_delegate: Pubkey, // Default key means no delegate
+    pub permissionless_claims: bool,
+    pub crank_tip_bps: u16,
+    pub acceleration_policy: AccelerationPolicy,
+    pub accelerated_amount: u64, // Unvested tokens pulled forward on acceleration
+    pub accelerated_at: i64,
+    pub vested_at_acceleration: u64, // Regular vesting reached when acceleration applied
+    pub option_terms: OptionTerms, // Default terms mean the grant is an RSU
+    pub amendment_count: u32,
+    pub last_amended: i64,
+    pub paused_at: i64, // 0 unless the beneficiary is currently on leave
+    pub total_paused_duration: i64,
+    pub template: Pubkey, // Template and version the terms were copied from, if any
+    pub template_version: u32,
+    pub staked_amount: u64, // Unvested escrow currently in the mint's staking pool
+    pub staked_at: i64, // Last time staking rewards were accrued
+    pub stake_rewards_accrued: u64,
+    pub stake_reward_carry: u64, // Sub-unit remainder of the last accrual, in reward numerator units
+    pub withholding: WithholdingTerms, // Company terms at grant time unless changed since
+    pub employee: Pubkey, // Employee record the grant belongs to, default if unlinked
     pub created_at: i64,
     pub last_claimed: i64,
     pub bump: u8,
 }
 
+#[account]
+pub struct BeneficiaryGrants {
+    pub company: Pubkey,
+    pub beneficiary: Pubkey,
+    pub grant_count: u64, // Next grant id, never reused
+    pub active_grants: u8,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Employee {
+    pub company: Pubkey,
+    pub wallet: Pubkey,
+    pub department: [u8; 32],
+    pub role: [u8; 32],
+    pub status: EmployeeStatus,
+    pub hire_date: i64,
+    pub termination_date: i64, // 0 while employed
+    pub leaver_policy: Option<LeaverPolicy>, // Set when termination revokes linked schedules
+    pub schedules_linked: u32,
+    pub schedules_revoked: u32,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct VestingTemplate {
+    pub company: Pubkey,
+    pub template_id: u32,
+    pub version: u32, // Bumped on every edit, schedules keep the terms they were created with
+    pub terms: TemplateTerms,
+    pub schedules_created: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Milestone {
+    pub schedule: Pubkey,
+    pub index: u8,
+    pub weight_bps: u16,
+    pub attester: Pubkey,
+    pub is_achieved: bool,
+    pub achieved_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct PerformanceMetric {
+    pub company: Pubkey,
+    pub metric_id: u32,
+    pub reporter: Pubkey,
+    pub current_value: u64,
+    pub peak_value: u64,
+    pub last_reported: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct MerkleDistributor {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub distributor_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub total_claimed: u64,
+    pub recipients_claimed: u64,
+    pub expires_at: i64, // Claims close here and the unclaimed remainder can be clawed back
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct DistributorClaim {
+    pub distributor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub last_claimed: i64,
+    pub bump: u8,
+}
+
 #[account]
 pub struct BankingAccount {
     pub owner: Pubkey,
@@ -120,6 +252,94 @@ pub enum VestingType {
     Hybrid,
 }
 
+/// Composable release curve: a time-based portion unlocked every `release_interval`
+/// seconds after the cliff (0 streams per second), plus `milestone_allocation_bps`
+/// of the grant reserved for milestone tranches
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReleaseTerms {
+    pub release_interval: i64,
+    pub milestone_allocation_bps: u16,
+}
+
+/// Payout tranche unlocked once the referenced metric reaches `threshold`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerformanceBand {
+    pub threshold: u64,
+    pub payout_bps: u16,
+}
+
+/// Turns a grant into options: vested tokens are bought at `strike_price`
+/// payment-mint base units per whole token instead of being claimed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptionTerms {
+    pub strike_price: u64,
+    pub payment_mint: Pubkey,
+    pub exercise_window: i64, // Seconds after termination vested options stay exercisable
+}
+
+/// Share of every claim sent to the company's payroll token account instead of the beneficiary
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WithholdingTerms {
+    pub withholding_bps: u16,
+    pub destination: Pubkey, // Payroll PDA's token account for the schedule's mint
+}
+
+/// How a company's declared supply is issued when it is created
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyPolicy {
+    pub mint_to_treasury: bool, // Mint total_supply into the company's ATA up front
+    pub retain_mint_authority: bool, // Needed for any later mint_additional_supply
+    pub supply_cap: u64, // 0 fixes the supply at total_supply
+}
+
+/// Grant terms HR reuses across many schedules
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateTerms {
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub vesting_type: VestingType,
+    pub release_terms: ReleaseTerms,
+    pub acceleration_policy: AccelerationPolicy,
+}
+
+/// Which events have to occur before a schedule accelerates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AccelerationTrigger {
+    #[default]
+    None,
+    /// Accelerates as soon as the company declares a change of control
+    SingleTrigger,
+    /// Also requires the beneficiary to be let go within the window after the change of control
+    DoubleTrigger,
+}
+
+/// Share of the still-unvested grant that vests immediately once triggered
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccelerationPolicy {
+    pub trigger: AccelerationTrigger,
+    pub acceleration_bps: u16,
+}
+
+/// How the vested portion of a schedule is settled when it is revoked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LeaverPolicy {
+    /// Vested tokens are paid out to the beneficiary as part of the revocation
+    GoodLeaver,
+    /// Vested tokens stay in escrow and remain claimable by the beneficiary
+    GoodLeaverDeferred,
+    /// Vested but unclaimed tokens are forfeited back to the company
+    BadLeaver,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EmployeeStatus {
+    Active,
+    /// Still employed, vesting is paused separately through pause_vesting_schedule
+    OnLeave,
+    /// Final, the record is kept for the linked schedules
+    Terminated,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum AccountType {
     Basic,
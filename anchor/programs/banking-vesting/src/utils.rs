@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::Mint;
 use crate::constants::*;
 use crate::errors::BankingVestingError;
+use crate::state::{
+    AccelerationPolicy, AccelerationTrigger, Company, EmployeeStatus, LeaverPolicy, PerformanceBand,
+    ReleaseTerms, TemplateTerms, VestingSchedule, VestingType, WithholdingTerms,
+};
 
 pub fn calculate_vested_amount(
     total_amount: u64,
@@ -23,15 +32,486 @@ pub fn calculate_vested_amount(
         return Ok(total_amount);
     }
 
-    let vested_amount = total_amount
-        .checked_mul(elapsed_time as u64)
+    // Widened so large grants over long durations can't overflow the product
+    let vested_amount = (total_amount as u128)
+        .checked_mul(elapsed_time as u128)
         .ok_or(BankingVestingError::ArithmeticOverflow)?
-        .checked_div(vesting_duration as u64)
+        .checked_div(vesting_duration as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(vested_amount as u64)
+}
+
+pub fn calculate_stepped_vested_amount(
+    total_amount: u64,
+    start_time: i64,
+    cliff_duration: i64,
+    vesting_duration: i64,
+    release_interval: i64,
+    current_time: i64,
+) -> Result<u64> {
+    if release_interval <= 0 {
+        return calculate_vested_amount(
+            total_amount,
+            start_time,
+            cliff_duration,
+            vesting_duration,
+            current_time,
+        );
+    }
+
+    if current_time < start_time {
+        return Ok(0);
+    }
+
+    let elapsed_time = current_time - start_time;
+
+    if elapsed_time < cliff_duration {
+        return Ok(0);
+    }
+
+    if elapsed_time >= vesting_duration {
+        return Ok(total_amount);
+    }
+
+    // Only whole release intervals count towards the unlocked amount, the final
+    // step at `vesting_duration` releases everything including rounding dust
+    let stepped_time = elapsed_time / release_interval * release_interval;
+
+    let vested_amount = (total_amount as u128)
+        .checked_mul(stepped_time as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(vesting_duration as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(vested_amount as u64)
+}
+
+/// Evaluates any schedule as a time-based portion released per `release_terms`
+/// plus a milestone (or performance) portion unlocked by basis points achieved,
+/// topped up by whatever a change-of-control acceleration pulled forward.
+pub fn calculate_schedule_vested_amount(
+    schedule: &VestingSchedule,
+    current_time: i64,
+) -> Result<u64> {
+    // Revocation settles the schedule, whatever is left in escrow belongs to the beneficiary
+    if schedule.is_revoked {
+        return Ok(schedule.total_amount);
+    }
+
+    let regular_vested = calculate_unaccelerated_vested_amount(schedule, current_time)?;
+
+    if schedule.accelerated_at == 0 {
+        return Ok(regular_vested.min(schedule.total_amount));
+    }
+
+    // Acceleration re-bases the schedule: what was unvested at `accelerated_at`
+    // minus the amount pulled forward keeps vesting along the rest of the regular curve
+    let base_vested = schedule.vested_at_acceleration;
+    let unvested_at_acceleration = schedule.total_amount.saturating_sub(base_vested);
+
+    if unvested_at_acceleration == 0 {
+        return Ok(schedule.total_amount);
+    }
+
+    let remaining_amount = unvested_at_acceleration.saturating_sub(schedule.accelerated_amount);
+
+    let rebased_vested = (regular_vested.saturating_sub(base_vested) as u128)
+        .checked_mul(remaining_amount as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(unvested_at_acceleration as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)? as u64;
+
+    let vested_amount = base_vested
+        .checked_add(schedule.accelerated_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_add(rebased_vested)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(vested_amount.min(schedule.total_amount))
+}
+
+/// Clock the schedule vests against: frozen while on leave and shifted back by
+/// every completed leave, which also pushes out the cliff and end date
+pub fn calculate_effective_vesting_time(schedule: &VestingSchedule, current_time: i64) -> i64 {
+    let frozen_time = if schedule.paused_at != 0 {
+        current_time.min(schedule.paused_at)
+    } else {
+        current_time
+    };
+
+    frozen_time.saturating_sub(schedule.total_paused_duration)
+}
+
+fn calculate_unaccelerated_vested_amount(
+    schedule: &VestingSchedule,
+    current_time: i64,
+) -> Result<u64> {
+    let current_time = calculate_effective_vesting_time(schedule, current_time);
+
+
+    // Performance tranches unlock like milestones, gated on the cached band instead
+    if schedule.vesting_type == VestingType::Performance {
+        return calculate_milestone_vested_amount(
+            schedule.total_amount,
+            schedule.performance_unlocked_bps,
+            BASIS_POINTS as u16,
+            schedule.start_time,
+            schedule.cliff_duration,
+            current_time,
+        );
+    }
+
+    let release_terms = schedule.release_terms;
+
+    let milestone_portion = (schedule.total_amount as u128)
+        .checked_mul(release_terms.milestone_allocation_bps as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)? as u64;
+
+    let time_portion = schedule.total_amount
+        .checked_sub(milestone_portion)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    let time_vested = calculate_stepped_vested_amount(
+        time_portion,
+        schedule.start_time,
+        schedule.cliff_duration,
+        schedule.vesting_duration,
+        release_terms.release_interval,
+        current_time,
+    )?;
+
+    let milestone_vested = calculate_milestone_vested_amount(
+        milestone_portion,
+        schedule.milestone_achieved_bps,
+        release_terms.milestone_allocation_bps,
+        schedule.start_time,
+        schedule.cliff_duration,
+        current_time,
+    )?;
+
+    let vested_amount = time_vested
+        .checked_add(milestone_vested)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
 
     Ok(vested_amount)
 }
 
+pub fn calculate_claimable_amount(schedule: &VestingSchedule, current_time: i64) -> Result<u64> {
+    let vested_amount = calculate_schedule_vested_amount(schedule, current_time)?;
+
+    let claimable_amount = vested_amount
+        .checked_sub(schedule.claimed_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(claimable_amount)
+}
+
+/// When the next tokens unlock and when the whole grant is vested, 0 where the
+/// answer depends on future milestones, metrics or a leave that hasn't ended
+pub fn calculate_vesting_timeline(schedule: &VestingSchedule, current_time: i64) -> Result<(i64, i64)> {
+    // Revocation settles whatever had vested at that point
+    if schedule.is_revoked {
+        return Ok((0, schedule.revoked_at));
+    }
+
+    // Completed leaves shift every time-based milestone of the schedule
+    let effective_start = schedule.start_time
+        .checked_add(schedule.total_paused_duration)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    let time_end = effective_start
+        .checked_add(schedule.vesting_duration)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    let vested_amount = calculate_schedule_vested_amount(schedule, current_time)?;
+
+    // Acceleration can complete a grant ahead of its regular end date
+    if vested_amount >= schedule.total_amount {
+        return Ok((0, current_time.min(time_end)));
+    }
+
+    if schedule.paused_at != 0 || schedule.vesting_type == VestingType::Performance {
+        return Ok((0, 0));
+    }
+
+    let release_terms = schedule.release_terms;
+    let elapsed_time = current_time - effective_start;
+
+    let next_unlock_time = if release_terms.milestone_allocation_bps as u64 >= BASIS_POINTS {
+        0
+    } else if elapsed_time < schedule.cliff_duration {
+        effective_start + schedule.cliff_duration
+    } else if elapsed_time >= schedule.vesting_duration {
+        0
+    } else if release_terms.release_interval > 0 {
+        let next_step = (elapsed_time / release_terms.release_interval + 1)
+            .checked_mul(release_terms.release_interval)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        effective_start + next_step.min(schedule.vesting_duration)
+    } else {
+        // Linear schedules unlock continuously
+        current_time
+    };
+
+    let milestones_outstanding = release_terms.milestone_allocation_bps > 0
+        && schedule.milestone_achieved_bps < release_terms.milestone_allocation_bps;
+
+    let fully_vested_at = if milestones_outstanding { 0 } else { time_end };
+
+    Ok((next_unlock_time, fully_vested_at))
+}
+
+pub fn calculate_milestone_vested_amount(
+    milestone_portion: u64,
+    achieved_bps: u16,
+    allocation_bps: u16,
+    start_time: i64,
+    cliff_duration: i64,
+    current_time: i64,
+) -> Result<u64> {
+    if allocation_bps == 0 {
+        return Ok(0);
+    }
+
+    // Achieved tranches still wait for the schedule start and cliff
+    if current_time < start_time || current_time - start_time < cliff_duration {
+        return Ok(0);
+    }
+
+    let vested_amount = (milestone_portion as u128)
+        .checked_mul(achieved_bps as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(allocation_bps as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(vested_amount as u64)
+}
+
+/// Vests `acceleration_bps` of what is still unvested at `accelerate_at` and
+/// records it on the schedule. Returns the amount pulled forward.
+pub fn apply_acceleration(schedule: &mut VestingSchedule, accelerate_at: i64) -> Result<u64> {
+    let vested_amount = calculate_unaccelerated_vested_amount(schedule, accelerate_at)?;
+
+    let unvested_amount = schedule.total_amount
+        .checked_sub(vested_amount)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    let accelerated_amount = (unvested_amount as u128)
+        .checked_mul(schedule.acceleration_policy.acceleration_bps as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)? as u64;
+
+    schedule.accelerated_amount = accelerated_amount;
+    schedule.accelerated_at = accelerate_at;
+    schedule.vested_at_acceleration = vested_amount;
+
+    Ok(accelerated_amount)
+}
+
+pub fn validate_acceleration_policy(policy: &AccelerationPolicy) -> Result<()> {
+    let is_valid = match policy.trigger {
+        AccelerationTrigger::None => policy.acceleration_bps == 0,
+        AccelerationTrigger::SingleTrigger | AccelerationTrigger::DoubleTrigger => {
+            policy.acceleration_bps > 0 && policy.acceleration_bps as u64 <= BASIS_POINTS
+        }
+    };
+
+    require!(is_valid, BankingVestingError::InvalidAccelerationPolicy);
+
+    Ok(())
+}
+
+pub fn validate_template_terms(terms: &TemplateTerms) -> Result<()> {
+    validate_vesting_durations(terms.cliff_duration, terms.vesting_duration)?;
+    validate_release_terms(terms.vesting_type, &terms.release_terms, terms.vesting_duration)?;
+    validate_acceleration_policy(&terms.acceleration_policy)
+}
+
+pub fn vesting_type_str(vesting_type: VestingType) -> &'static str {
+    match vesting_type {
+        VestingType::Linear => "Linear",
+        VestingType::Cliff => "Cliff",
+        VestingType::Milestone => "Milestone",
+        VestingType::Performance => "Performance",
+        VestingType::Hybrid => "Hybrid",
+    }
+}
+
+pub fn acceleration_trigger_str(trigger: AccelerationTrigger) -> &'static str {
+    match trigger {
+        AccelerationTrigger::None => "None",
+        AccelerationTrigger::SingleTrigger => "SingleTrigger",
+        AccelerationTrigger::DoubleTrigger => "DoubleTrigger",
+    }
+}
+
+pub fn leaver_policy_str(leaver_policy: LeaverPolicy) -> &'static str {
+    match leaver_policy {
+        LeaverPolicy::GoodLeaver => "GoodLeaver",
+        LeaverPolicy::GoodLeaverDeferred => "GoodLeaverDeferred",
+        LeaverPolicy::BadLeaver => "BadLeaver",
+    }
+}
+
+pub fn employee_status_str(status: EmployeeStatus) -> &'static str {
+    match status {
+        EmployeeStatus::Active => "Active",
+        EmployeeStatus::OnLeave => "OnLeave",
+        EmployeeStatus::Terminated => "Terminated",
+    }
+}
+
+/// Zero-padded name the company is stored and seeded under
+pub fn company_name_seed(name: &str) -> [u8; MAX_COMPANY_NAME_LENGTH] {
+    let mut name_bytes = [0u8; MAX_COMPANY_NAME_LENGTH];
+    let name_len = name.len().min(MAX_COMPANY_NAME_LENGTH);
+    name_bytes[..name_len].copy_from_slice(&name.as_bytes()[..name_len]);
+    name_bytes
+}
+
+/// Withheld tokens may only go to the company's payroll token account
+pub fn validate_withholding_terms(terms: &WithholdingTerms, payroll_token_account: &Pubkey) -> Result<()> {
+    require!(
+        terms.withholding_bps <= MAX_WITHHOLDING_BPS,
+        BankingVestingError::InvalidWithholdingTerms
+    );
+    require!(
+        terms.withholding_bps == 0 || terms.destination == *payroll_token_account,
+        BankingVestingError::InvalidWithholdingTerms
+    );
+
+    Ok(())
+}
+
+/// Counts distinct members of the company's signer set that signed: the
+/// authority itself plus any co-signers passed as remaining accounts
+pub fn require_signer_approval(
+    company: &Company,
+    authority: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if company.approval_threshold == 0 {
+        return Ok(());
+    }
+    
+    let signer_set = &company.signer_set[..company.signer_count as usize];
+    let mut approved = [false; MAX_COMPANY_SIGNERS];
+    
+    let signers = std::iter::once(*authority).chain(
+        remaining_accounts
+            .iter()
+            .filter(|account| account.is_signer)
+            .map(|account| account.key()),
+    );
+    
+    for signer in signers {
+        if let Some(index) = signer_set.iter().position(|member| *member == signer) {
+            approved[index] = true;
+        }
+    }
+    
+    let approvals = approved.iter().filter(|approved| **approved).count();
+    
+    require!(
+        approvals >= company.approval_threshold as usize,
+        BankingVestingError::InsufficientApprovals
+    );
+    
+    Ok(())
+}
+
+/// Grants above the company's grant threshold need the signer set's approval
+pub fn require_grant_approval(
+    company: &Company,
+    authority: &Pubkey,
+    total_amount: u64,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if total_amount > company.grant_approval_threshold {
+        require_signer_approval(company, authority, remaining_accounts)?;
+    }
+    
+    Ok(())
+}
+
+pub fn calculate_withholding_amount(amount: u64, withholding_bps: u16) -> Result<u64> {
+    let withheld_amount = (amount as u128)
+        .checked_mul(withholding_bps as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(BASIS_POINTS as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    Ok(withheld_amount as u64)
+}
+
+/// Strike × quantity, rounded up so small exercises are never free
+pub fn calculate_exercise_cost(strike_price: u64, quantity: u64, decimals: u8) -> Result<u64> {
+    let unit = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    let exercise_cost = (strike_price as u128)
+        .checked_mul(quantity as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_add(unit - 1)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?
+        .checked_div(unit)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+
+    u64::try_from(exercise_cost).map_err(|_| BankingVestingError::ArithmeticOverflow.into())
+}
+
+pub fn calculate_performance_unlocked_bps(bands: &[PerformanceBand], metric_value: u64) -> u16 {
+    bands
+        .iter()
+        .filter(|band| metric_value >= band.threshold)
+        .map(|band| band.payout_bps)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn refresh_performance_unlock(schedule: &mut VestingSchedule, peak_value: u64) {
+    let band_count = schedule.performance_band_count as usize;
+    let band_bps = calculate_performance_unlocked_bps(&schedule.performance_bands[..band_count], peak_value);
+
+    // Bands only ratchet up so a later metric dip never claws back vested tokens
+    schedule.performance_unlocked_bps = schedule.performance_unlocked_bps.max(band_bps);
+}
+
+/// Schedules tied to a metric have to be evaluated against it, leaving the
+/// account out would settle them on a stale performance unlock
+pub fn require_performance_metric<T>(schedule: &VestingSchedule, performance_metric: Option<&T>) -> Result<()> {
+    require!(
+        schedule.performance_metric == Pubkey::default() || performance_metric.is_some(),
+        BankingVestingError::AccountNotFound
+    );
+
+    Ok(())
+}
+
+pub fn validate_performance_bands(bands: &[PerformanceBand]) -> Result<()> {
+    if bands.is_empty() || bands.len() > MAX_PERFORMANCE_BANDS {
+        return Err(BankingVestingError::InvalidPerformanceBands.into());
+    }
+
+    // Thresholds and payouts must both increase so higher bands always pay more
+    for window in bands.windows(2) {
+        if window[1].threshold <= window[0].threshold || window[1].payout_bps <= window[0].payout_bps {
+            return Err(BankingVestingError::InvalidPerformanceBands.into());
+        }
+    }
+
+    if bands[0].payout_bps == 0 || bands[bands.len() - 1].payout_bps as u64 > BASIS_POINTS {
+        return Err(BankingVestingError::InvalidPerformanceBands.into());
+    }
+
+    Ok(())
+}
+
 pub fn calculate_compound_interest(
     principal: u64, 
     apy_rate: u16,
@@ -99,23 +579,63 @@ pub fn calculate_liquidation_health(
     Ok(health_ratio as u16)
 }
 
+/// Pro-rata rewards for `time_staked` seconds, rounded down to whole base units
 pub fn calculate_staking_rewards(
     staked_amount: u64,
     apy_rate: u16,
     time_staked: i64,
 ) -> Result<u64> {
-    let annual_rate = apy_rate as u64;
-    let time_fraction = time_staked as u64 / SECONDS_PER_YEAR as u64;
+    let rewards = calculate_staking_reward_numerator(staked_amount, apy_rate, time_staked)?
+        .checked_div(staking_reward_denominator())
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
     
-    let rewards = staked_amount
-        .checked_mul(annual_rate)
-        .ok_or(BankingVestingError::ArithmeticOverflow)?
-        .checked_mul(time_fraction)
+    u64::try_from(rewards).map_err(|_| BankingVestingError::ArithmeticOverflow.into())
+}
+
+fn calculate_staking_reward_numerator(
+    staked_amount: u64,
+    apy_rate: u16,
+    time_staked: i64,
+) -> Result<u128> {
+    let numerator = (staked_amount as u128)
+        .checked_mul(apy_rate as u128)
         .ok_or(BankingVestingError::ArithmeticOverflow)?
-        .checked_div(BASIS_POINTS)
+        .checked_mul(time_staked.max(0) as u128)
+        .ok_or(BankingVestingError::ArithmeticOverflow)?;
+    
+    Ok(numerator)
+}
+
+fn staking_reward_denominator() -> u128 {
+    BASIS_POINTS as u128 * SECONDS_PER_YEAR as u128
+}
+
+/// Books rewards earned by staked escrow since the last checkpoint, carrying
+/// the sub-unit remainder so frequent checkpoints don't round rewards away
+pub fn accrue_stake_rewards(schedule: &mut VestingSchedule, apy_rate: u16, current_time: i64) -> Result<()> {
+    if schedule.staked_amount > 0 {
+        let numerator = calculate_staking_reward_numerator(
+            schedule.staked_amount,
+            apy_rate,
+            current_time - schedule.staked_at,
+        )?
+        .checked_add(schedule.stake_reward_carry as u128)
         .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        
+        let denominator = staking_reward_denominator();
+        let rewards = u64::try_from(numerator / denominator)
+            .map_err(|_| BankingVestingError::ArithmeticOverflow)?;
+        
+        schedule.stake_rewards_accrued = schedule.stake_rewards_accrued
+            .checked_add(rewards)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        // Always below the denominator, which fits in a u64
+        schedule.stake_reward_carry = (numerator % denominator) as u64;
+    }
+    
+    schedule.staked_at = current_time;
     
-    Ok(rewards)
+    Ok(())
 }
 
 pub fn calculate_platform_fee(amount: u64, fee_bps: u16) -> Result<u64> {
@@ -133,6 +653,18 @@ pub fn validate_vesting_parameters(
     cliff_duration: i64,
     vesting_duration: i64,
 ) -> Result<()> {
+    validate_vesting_durations(cliff_duration, vesting_duration)?;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    if start_time < current_time - SECONDS_PER_DAY {
+        return Err(BankingVestingError::InvalidTimestamp.into());
+    }
+    
+    Ok(())
+}
+
+/// Duration bounds shared by creation and amendments, the latter keep their original start
+pub fn validate_vesting_durations(cliff_duration: i64, vesting_duration: i64) -> Result<()> {
     if cliff_duration < MIN_CLIFF_DURATION || cliff_duration > MAX_CLIFF_DURATION {
         return Err(BankingVestingError::InvalidVestingParameters.into());
     }
@@ -145,11 +677,36 @@ pub fn validate_vesting_parameters(
         return Err(BankingVestingError::InvalidVestingParameters.into());
     }
     
-    let current_time = Clock::get()?.unix_timestamp;
-    if start_time < current_time - SECONDS_PER_DAY {
-        return Err(BankingVestingError::InvalidTimestamp.into());
+    Ok(())
+}
+
+pub fn validate_release_terms(
+    vesting_type: VestingType,
+    release_terms: &ReleaseTerms,
+    vesting_duration: i64,
+) -> Result<()> {
+    let release_interval = release_terms.release_interval;
+    if release_interval != 0
+        && (release_interval < MIN_RELEASE_INTERVAL || release_interval > vesting_duration)
+    {
+        return Err(BankingVestingError::InvalidReleaseTerms.into());
     }
-    
+
+    let is_valid = match vesting_type {
+        VestingType::Linear | VestingType::Cliff => release_terms.milestone_allocation_bps == 0,
+        VestingType::Hybrid => (release_terms.milestone_allocation_bps as u64) < BASIS_POINTS,
+        VestingType::Milestone => {
+            release_interval == 0
+                && (release_terms.milestone_allocation_bps == 0
+                    || release_terms.milestone_allocation_bps as u64 == BASIS_POINTS)
+        }
+        VestingType::Performance => *release_terms == ReleaseTerms::default(),
+    };
+
+    if !is_valid {
+        return Err(BankingVestingError::InvalidReleaseTerms.into());
+    }
+
     Ok(())
 }
 
@@ -160,12 +717,77 @@ pub fn validate_apy_rate(apy_rate: u16) -> Result<()> {
     Ok(())
 }
 
+fn transfer_fee_config(mint: &InterfaceAccount<Mint>) -> Result<Option<TransferFeeConfig>> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+
+    Ok(mint_state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Whether the mint can charge a fee on transfers, now or after a fee update
+pub fn has_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<bool> {
+    Ok(transfer_fee_config(mint)?.is_some())
+}
+
+/// Fee a Token-2022 transfer-fee mint takes out of a transfer of `amount`.
+/// Interest-bearing mints need nothing here: they only change the UI amount,
+/// while every amount this program stores is in raw base units.
+pub fn calculate_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow.into()),
+        None => Ok(0),
+    }
+}
+
+/// What the destination is actually credited when `amount` is sent
+pub fn calculate_received_amount(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let fee = calculate_transfer_fee(mint, amount)?;
+
+    amount
+        .checked_sub(fee)
+        .ok_or(BankingVestingError::ArithmeticOverflow.into())
+}
+
+/// What has to be sent for the destination to be credited `received_amount`
+pub fn calculate_gross_transfer_amount(mint: &InterfaceAccount<Mint>, received_amount: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, received_amount)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?,
+        None => 0,
+    };
+
+    received_amount
+        .checked_add(fee)
+        .ok_or(BankingVestingError::ArithmeticOverflow.into())
+}
+
+/// Converts an amount expressed for a BASE_TOKEN_DECIMALS mint to a mint with `decimals`
+pub fn scale_to_decimals(amount: u64, decimals: u8) -> Result<u64> {
+    if decimals >= BASE_TOKEN_DECIMALS {
+        let factor = 10u64
+            .checked_pow((decimals - BASE_TOKEN_DECIMALS) as u32)
+            .ok_or(BankingVestingError::ArithmeticOverflow)?;
+        amount
+            .checked_mul(factor)
+            .ok_or(BankingVestingError::ArithmeticOverflow.into())
+    } else {
+        let factor = 10u64.pow((BASE_TOKEN_DECIMALS - decimals) as u32);
+        // Never let a minimum round down to zero
+        Ok(std::cmp::max(amount / factor, 1))
+    }
+}
+
 pub fn validate_loan_parameters(
     amount: u64,
     collateral_amount: u64,
     duration: i64,
+    decimals: u8,
 ) -> Result<()> {
-    if amount < MIN_LOAN_AMOUNT {
+    if amount < scale_to_decimals(MIN_LOAN_AMOUNT, decimals)? {
         return Err(BankingVestingError::InvalidAmount.into());
     }
     
@@ -185,3 +807,122 @@ pub fn validate_loan_parameters(
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000;
+    const DURATION: i64 = 1_000;
+
+    fn linear_schedule(total_amount: u64) -> VestingSchedule {
+        VestingSchedule {
+            company: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            grant_id: 0,
+            mint: Pubkey::new_unique(),
+            total_amount,
+            claimed_amount: 0,
+            start_time: START,
+            cliff_duration: 0,
+            vesting_duration: DURATION,
+            vesting_type: VestingType::Linear,
+            release_terms: ReleaseTerms::default(),
+            is_revoked: false,
+            revoked_at: 0,
+            milestone_count: 0,
+            milestone_total_bps: 0,
+            milestone_achieved_bps: 0,
+            performance_metric: Pubkey::default(),
+            performance_bands: [PerformanceBand::default(); MAX_PERFORMANCE_BANDS],
+            performance_band_count: 0,
+            performance_unlocked_bps: 0,
+            claim_delegate: Pubkey::default(),
+            permissionless_claims: false,
+            crank_tip_bps: 0,
+            acceleration_policy: AccelerationPolicy {
+                trigger: AccelerationTrigger::SingleTrigger,
+                acceleration_bps: 5_000,
+            },
+            accelerated_amount: 0,
+            accelerated_at: 0,
+            vested_at_acceleration: 0,
+            option_terms: Default::default(),
+            amendment_count: 0,
+            last_amended: 0,
+            paused_at: 0,
+            total_paused_duration: 0,
+            template: Pubkey::default(),
+            template_version: 0,
+            staked_amount: 0,
+            staked_at: 0,
+            stake_rewards_accrued: 0,
+            stake_reward_carry: 0,
+            withholding: WithholdingTerms::default(),
+            employee: Pubkey::default(),
+            created_at: START,
+            last_claimed: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn acceleration_at_start_rebases_remaining_grant() {
+        let mut schedule = linear_schedule(1_000);
+        assert_eq!(apply_acceleration(&mut schedule, START).unwrap(), 500);
+
+        let vested_at = |time| calculate_schedule_vested_amount(&schedule, time).unwrap();
+        assert_eq!(vested_at(START), 500);
+        assert_eq!(vested_at(START + DURATION / 2), 750);
+        assert_eq!(vested_at(START + DURATION), 1_000);
+    }
+
+    #[test]
+    fn acceleration_mid_schedule_keeps_vested_amount_continuous() {
+        let mut schedule = linear_schedule(1_000);
+        let midpoint = START + DURATION / 2;
+        assert_eq!(apply_acceleration(&mut schedule, midpoint).unwrap(), 250);
+
+        let vested_at = |time| calculate_schedule_vested_amount(&schedule, time).unwrap();
+        assert_eq!(vested_at(midpoint), 750);
+        assert_eq!(vested_at(START + DURATION * 3 / 4), 875);
+        assert_eq!(vested_at(START + DURATION), 1_000);
+    }
+    #[test]
+    fn stake_rewards_accrue_pro_rata_across_checkpoints() {
+        let mut schedule = linear_schedule(10 * 10u64.pow(9));
+        schedule.staked_amount = 10u64.pow(9);
+        schedule.staked_at = START;
+
+        // 10% APY on one token for a day, checkpointed every minute
+        for minute in 1..=1_440 {
+            accrue_stake_rewards(&mut schedule, 1_000, START + minute * 60).unwrap();
+        }
+
+        let expected = calculate_staking_rewards(10u64.pow(9), 1_000, SECONDS_PER_DAY).unwrap();
+        assert_eq!(expected, 273_972);
+        assert_eq!(schedule.stake_rewards_accrued, expected);
+    }
+    #[test]
+    fn linear_vesting_handles_large_grants() {
+        // 1e12 base units eight months in overflows a u64 product
+        let total_amount = 10u64.pow(12);
+        let vesting_duration = 4 * SECONDS_PER_YEAR;
+        let elapsed = 8 * SECONDS_PER_MONTH;
+
+        let vested = calculate_vested_amount(total_amount, START, 0, vesting_duration, START + elapsed).unwrap();
+        assert_eq!(vested, (total_amount as u128 * elapsed as u128 / vesting_duration as u128) as u64);
+    }
+    #[test]
+    fn distributor_leaf_near_supply_cap_vests_without_overflow() {
+        // claim_from_distributor shares this path, leaves can carry most of a mint's supply
+        let total_amount = u64::MAX / 2;
+        let vesting_duration = SECONDS_PER_YEAR;
+
+        let halfway = calculate_vested_amount(total_amount, START, 0, vesting_duration, START + vesting_duration / 2).unwrap();
+        assert_eq!(halfway, total_amount / 2);
+
+        let done = calculate_vested_amount(total_amount, START, 0, vesting_duration, START + vesting_duration).unwrap();
+        assert_eq!(done, total_amount);
+    }
+}
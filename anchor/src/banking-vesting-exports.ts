@@ -15,13 +15,25 @@ export interface Platform {
 
 export interface Company {
   authority: PublicKey;
+  creator: PublicKey; // PDA seed, stays fixed when the authority rotates
+  pendingAuthority: PublicKey; // Default unless a transfer is awaiting acceptance
   name: Uint8Array; // Fixed size [32]
   symbol: Uint8Array; // Fixed size [8]
   mint: PublicKey;
-  totalSupply: bigint;
+  totalSupply: bigint; // Declared supply, not necessarily minted yet
+  issuedSupply: bigint;
   allocatedSupply: bigint;
   employeesCount: bigint;
   vestingSchedulesCount: bigint;
+  requireTransferApproval: boolean;
+  changeOfControlAt: bigint;
+  withholding: WithholdingTerms; // Copied onto every new grant
+  supplyCap: bigint;
+  mintAuthorityRetained: boolean;
+  signerSet: PublicKey[]; // Fixed size [5], first signerCount entries are in use
+  signerCount: number;
+  approvalThreshold: number; // 0 disables the signer set
+  grantApprovalThreshold: bigint;
   createdAt: bigint;
   bump: number;
 }
@@ -29,6 +41,7 @@ export interface Company {
 export interface VestingSchedule {
   company: PublicKey;
   beneficiary: PublicKey;
+  grantId: bigint;
   mint: PublicKey;
   totalAmount: bigint;
   claimedAmount: bigint;
@@ -36,12 +49,180 @@ export interface VestingSchedule {
   cliffDuration: bigint;
   vestingDuration: bigint;
   vestingType: VestingType;
+  releaseTerms: ReleaseTerms;
   isRevoked: boolean;
+  revokedAt: bigint;
+  milestoneCount: number;
+  milestoneTotalBps: number;
+  milestoneAchievedBps: number;
+  performanceMetric: PublicKey;
+  performanceBands: PerformanceBand[];
+  performanceBandCount: number;
+  performanceUnlockedBps: number;
+  claimDelegate: PublicKey;
+  permissionlessClaims: boolean;
+  crankTipBps: number;
+  accelerationPolicy: AccelerationPolicy;
+  acceleratedAmount: bigint;
+  acceleratedAt: bigint;
+  vestedAtAcceleration: bigint;
+  optionTerms: OptionTerms;
+  amendmentCount: number;
+  lastAmended: bigint;
+  pausedAt: bigint;
+  totalPausedDuration: bigint;
+  template: PublicKey;
+  templateVersion: number;
+  stakedAmount: bigint;
+  stakedAt: bigint;
+  stakeRewardsAccrued: bigint;
+  stakeRewardCarry: bigint;
+  withholding: WithholdingTerms; // Company terms at grant time unless changed since
+  employee: PublicKey; // Default when not linked to an employee record
   createdAt: bigint;
   lastClaimed: bigint;
   bump: number;
 }
 
+// Returned by quote_vesting_schedule through return data
+export interface VestingQuote {
+  vestedAmount: bigint;
+  claimedAmount: bigint;
+  claimableAmount: bigint;
+  nextUnlockTime: bigint; // 0 when not determined by time alone
+  fullyVestedAt: bigint; // 0 when not determined by time alone
+}
+
+export interface Employee {
+  company: PublicKey;
+  wallet: PublicKey;
+  department: Uint8Array;
+  role: Uint8Array;
+  status: EmployeeStatus;
+  hireDate: bigint;
+  terminationDate: bigint; // 0 while employed
+  leaverPolicy: LeaverPolicy | null; // Set when termination revokes linked schedules
+  schedulesLinked: number;
+  schedulesRevoked: number;
+  createdAt: bigint;
+  updatedAt: bigint;
+  bump: number;
+}
+
+export interface BeneficiaryGrants {
+  company: PublicKey;
+  beneficiary: PublicKey;
+  grantCount: bigint;
+  activeGrants: number;
+  bump: number;
+}
+
+export interface TemplateTerms {
+  cliffDuration: bigint;
+  vestingDuration: bigint;
+  vestingType: VestingType;
+  releaseTerms: ReleaseTerms;
+  accelerationPolicy: AccelerationPolicy;
+}
+
+export interface VestingTemplate {
+  company: PublicKey;
+  templateId: number;
+  version: number;
+  terms: TemplateTerms;
+  schedulesCreated: bigint;
+  createdAt: bigint;
+  updatedAt: bigint;
+  bump: number;
+}
+
+export interface Milestone {
+  schedule: PublicKey;
+  index: number;
+  weightBps: number;
+  attester: PublicKey;
+  isAchieved: boolean;
+  achievedAt: bigint;
+  createdAt: bigint;
+  bump: number;
+}
+
+export interface ReleaseTerms {
+  releaseInterval: bigint;
+  milestoneAllocationBps: number;
+}
+
+export interface OptionTerms {
+  strikePrice: bigint; // Payment mint base units per whole token
+  paymentMint: PublicKey;
+  exerciseWindow: bigint;
+}
+
+export interface SupplyPolicy {
+  mintToTreasury: boolean;
+  retainMintAuthority: boolean;
+  supplyCap: bigint; // 0 fixes the supply at totalSupply
+}
+
+export interface WithholdingTerms {
+  withholdingBps: number;
+  destination: PublicKey; // Payroll PDA's token account for the schedule's mint
+}
+
+export interface AccelerationPolicy {
+  trigger: AccelerationTrigger;
+  accelerationBps: number;
+}
+
+export interface PerformanceBand {
+  threshold: bigint;
+  payoutBps: number;
+}
+
+export interface PerformanceMetric {
+  company: PublicKey;
+  metricId: number;
+  reporter: PublicKey;
+  currentValue: bigint;
+  peakValue: bigint;
+  lastReported: bigint;
+  createdAt: bigint;
+  bump: number;
+}
+
+export interface MerkleDistributor {
+  company: PublicKey;
+  mint: PublicKey;
+  distributorId: bigint;
+  merkleRoot: Uint8Array;
+  totalAmount: bigint;
+  totalClaimed: bigint;
+  recipientsClaimed: bigint;
+  expiresAt: bigint; // Claims close here and the unclaimed remainder can be clawed back
+  createdAt: bigint;
+  bump: number;
+}
+
+export interface DistributorClaim {
+  distributor: PublicKey;
+  beneficiary: PublicKey;
+  totalAmount: bigint;
+  claimedAmount: bigint;
+  startTime: bigint;
+  cliffDuration: bigint;
+  vestingDuration: bigint;
+  lastClaimed: bigint;
+  bump: number;
+}
+
+export interface DistributorLeaf {
+  beneficiary: PublicKey;
+  amount: bigint;
+  startTime: bigint;
+  cliffDuration: bigint;
+  vestingDuration: bigint;
+}
+
 export interface BankingAccount {
   owner: PublicKey;
   balance: bigint;
@@ -114,6 +295,24 @@ export enum VestingType {
   Hybrid = 'Hybrid'
 }
 
+export enum LeaverPolicy {
+  GoodLeaver = 'GoodLeaver',
+  GoodLeaverDeferred = 'GoodLeaverDeferred',
+  BadLeaver = 'BadLeaver'
+}
+
+export enum EmployeeStatus {
+  Active = 'Active',
+  OnLeave = 'OnLeave',
+  Terminated = 'Terminated'
+}
+
+export enum AccelerationTrigger {
+  None = 'None',
+  SingleTrigger = 'SingleTrigger',
+  DoubleTrigger = 'DoubleTrigger'
+}
+
 export enum AccountType {
   Basic = 'Basic',
   Premium = 'Premium',
@@ -134,11 +333,26 @@ export enum LoanStatus {
 export const PLATFORM_SEED = 'platform';
 export const COMPANY_SEED = 'company';
 export const VESTING_SEED = 'vesting';
+export const GRANTS_SEED = 'grants';
 export const BANKING_SEED = 'banking';
 export const STAKING_SEED = 'staking';
 export const LOAN_SEED = 'loan';
 export const SAVINGS_SEED = 'savings';
 export const PROFILE_SEED = 'profile';
+export const MILESTONE_SEED = 'milestone';
+export const METRIC_SEED = 'metric';
+export const DISTRIBUTOR_SEED = 'distributor';
+export const DISTRIBUTOR_CLAIM_SEED = 'distributor_claim';
+export const TEMPLATE_SEED = 'template';
+export const EMPLOYEE_SEED = 'employee';
+export const PAYROLL_SEED = 'payroll';
+
+// Release interval presets (seconds)
+export const RELEASE_INTERVAL_MONTHLY = 2629746; // average Gregorian month
+export const RELEASE_INTERVAL_QUARTERLY = 3 * RELEASE_INTERVAL_MONTHLY;
+export const MAX_CRANK_TIP_BPS = 100; // 1%
+export const MAX_COMPANY_SIGNERS = 5;
+export const MAX_COMPANY_NAME_LENGTH = 32;
 
 // Helper Functions
 export function getPlatformPDA(): [PublicKey, number] {
@@ -148,16 +362,93 @@ export function getPlatformPDA(): [PublicKey, number] {
   );
 }
 
-export function getCompanyPDA(authority: PublicKey, name: Uint8Array): [PublicKey, number] {
+// Seeded by the creator, not the current authority, so the address survives rotation
+export function getCompanyPDA(creator: PublicKey, name: string): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(COMPANY_SEED), creator.toBuffer(), getCompanyNameSeed(name)],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+// Company names are stored and seeded zero-padded to MAX_COMPANY_NAME_LENGTH bytes
+export function getCompanyNameSeed(name: string): Buffer {
+  const nameSeed = Buffer.alloc(MAX_COMPANY_NAME_LENGTH);
+  nameSeed.write(name);
+  return nameSeed;
+}
+
+export function getBeneficiaryGrantsPDA(company: PublicKey, beneficiary: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(GRANTS_SEED), company.toBuffer(), beneficiary.toBuffer()],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getVestingSchedulePDA(
+  company: PublicKey,
+  beneficiary: PublicKey,
+  grantId: bigint
+): [PublicKey, number] {
+  const grantIdBytes = Buffer.alloc(8);
+  grantIdBytes.writeBigUInt64LE(grantId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(VESTING_SEED), company.toBuffer(), beneficiary.toBuffer(), grantIdBytes],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getMilestonePDA(schedule: PublicKey, index: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(MILESTONE_SEED), schedule.toBuffer(), Buffer.from([index])],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getPerformanceMetricPDA(company: PublicKey, metricId: number): [PublicKey, number] {
+  const metricIdBytes = Buffer.alloc(4);
+  metricIdBytes.writeUInt32LE(metricId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(METRIC_SEED), company.toBuffer(), metricIdBytes],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getMerkleDistributorPDA(company: PublicKey, distributorId: bigint): [PublicKey, number] {
+  const distributorIdBytes = Buffer.alloc(8);
+  distributorIdBytes.writeBigUInt64LE(distributorId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DISTRIBUTOR_SEED), company.toBuffer(), distributorIdBytes],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getDistributorClaimPDA(distributor: PublicKey, beneficiary: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DISTRIBUTOR_CLAIM_SEED), distributor.toBuffer(), beneficiary.toBuffer()],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getVestingTemplatePDA(company: PublicKey, templateId: number): [PublicKey, number] {
+  const templateIdBytes = Buffer.alloc(4);
+  templateIdBytes.writeUInt32LE(templateId);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(TEMPLATE_SEED), company.toBuffer(), templateIdBytes],
+    BANKING_VESTING_PROGRAM_ID
+  );
+}
+
+export function getEmployeePDA(company: PublicKey, wallet: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from(COMPANY_SEED), authority.toBuffer(), name],
+    [Buffer.from(EMPLOYEE_SEED), company.toBuffer(), wallet.toBuffer()],
     BANKING_VESTING_PROGRAM_ID
   );
 }
 
-export function getVestingSchedulePDA(company: PublicKey, beneficiary: PublicKey): [PublicKey, number] {
+// Owns the company's payroll token accounts, the only place withheld tokens can go
+export function getPayrollPDA(company: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from(VESTING_SEED), company.toBuffer(), beneficiary.toBuffer()],
+    [Buffer.from(PAYROLL_SEED), company.toBuffer()],
     BANKING_VESTING_PROGRAM_ID
   );
 }